@@ -9,7 +9,23 @@ fn main() {
         .with_builder(&["reservation.ReservationQuery", "reservation.FilterById"])
         .with_into_builder(
             "reservation.ReservationQuery",
-            &["user_id", "resource_id", "status", "start", "end", "desc"],
+            &[
+                "user_id",
+                "resource_id",
+                "status",
+                "start",
+                "end",
+                "desc",
+                "match_mode",
+                "resource_ids",
+                "user_ids",
+                "limit",
+                "location_id",
+                "sort_by",
+                "created_by",
+                "created_between",
+                "updated_between",
+            ],
         )
         .with_into_builder(
             "reservation.FilterById",
@@ -20,6 +36,15 @@ fn main() {
                 "cursor",
                 "page_size",
                 "desc",
+                "start",
+                "end",
+                "resource_ids",
+                "user_ids",
+                "location_id",
+                "sort_by",
+                "created_by",
+                "created_between",
+                "updated_between",
             ],
         )
         .compile(&["protos/reservation.proto"], &["protos"])
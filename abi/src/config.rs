@@ -1,11 +1,19 @@
 use crate::Error;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
+use std::{collections::HashMap, fs, path::Path};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
     pub db: DbConfig,
     pub server: SeverConfig,
+    #[serde(default)]
+    pub query: QueryConfig,
+    #[serde(default)]
+    pub quota: QuotaConfig,
+    #[serde(default)]
+    pub exclusion: ExclusionConfig,
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -29,10 +37,100 @@ pub struct SeverConfig {
     pub port: u16,
 }
 
+// server side limits for the streaming query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryConfig {
+    // max rows streamed back for one query, also the upper bound of ReservationQuery.limit
+    #[serde(default = "default_max_rows")]
+    pub max_rows: i64,
+    // max days between start and end of the query window, no limit if not set
+    #[serde(default)]
+    pub max_window_days: Option<i64>,
+    // statement timeout for the query, a shorter grpc deadline takes precedence
+    #[serde(default = "default_statement_timeout_ms")]
+    pub statement_timeout_ms: u64,
+}
+
+fn default_max_rows() -> i64 {
+    10000
+}
+
+fn default_statement_timeout_ms() -> u64 {
+    30000
+}
+
+impl Default for QueryConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: default_max_rows(),
+            max_window_days: None,
+            statement_timeout_ms: default_statement_timeout_ms(),
+        }
+    }
+}
+
+// per user booking quotas, checked when reserving. no limit if not set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    // pending or confirmed reservations which are not over yet
+    #[serde(default)]
+    pub max_active_reservations: Option<i64>,
+    // booked hours in a week (monday to sunday in the timezone of the resource), checked for every week
+    // a reservation overlaps
+    #[serde(default)]
+    pub max_hours_per_week: Option<i64>,
+    // same as max_active_reservations, but only counts the given resource type, e.g. "meeting-room": 2
+    #[serde(default)]
+    pub max_active_per_resource_type: HashMap<String, i64>,
+}
+
+impl QuotaConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_active_reservations.is_none()
+            && self.max_hours_per_week.is_none()
+            && self.max_active_per_resource_type.is_empty()
+    }
+}
+
+// opt-in rules to reject overlapping reservations across resources
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExclusionConfig {
+    // a user can't hold two resources of these types at the same time, e.g. ["meeting-room"]
+    #[serde(default)]
+    pub per_user_resource_types: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdempotencyConfig {
+    // how long the response of a request with an idempotency key is kept for replay
+    #[serde(default = "default_idempotency_ttl_seconds")]
+    pub ttl_seconds: i64,
+    // how long a key in progress is held without being renewed, it's renewed while the request runs
+    #[serde(default = "default_idempotency_lease_seconds")]
+    pub lease_seconds: i64,
+}
+
+fn default_idempotency_ttl_seconds() -> i64 {
+    24 * 60 * 60
+}
+
+fn default_idempotency_lease_seconds() -> i64 {
+    30
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: default_idempotency_ttl_seconds(),
+            lease_seconds: default_idempotency_lease_seconds(),
+        }
+    }
+}
+
 impl Config {
     pub fn load(filename: impl AsRef<Path>) -> Result<Self, Error> {
-        let config = fs::read_to_string(filename.as_ref()).map_err(|_| Error::FailedToParse)?;
-        serde_yaml::from_str(&config).map_err(|_| Error::FailedToRead)
+        let config = fs::read_to_string(filename.as_ref()).map_err(|_| Error::FailedToRead)?;
+        serde_yaml::from_str(&config).map_err(|_| Error::FailedToParse)
     }
 }
 
@@ -85,8 +183,21 @@ mod tests {
                 server: SeverConfig {
                     host: "0.0.0.0".to_string(),
                     port: 8080
-                }
+                },
+                query: QueryConfig::default(),
+                quota: QuotaConfig::default(),
+                exclusion: ExclusionConfig::default(),
+                idempotency: IdempotencyConfig::default(),
             }
         )
     }
+
+    #[test]
+    fn config_load_error_should_match_the_cause() {
+        let err = Config::load("../service/fixtures/not_exist.yml").unwrap_err();
+        assert!(matches!(err, Error::FailedToRead));
+
+        let err = Config::load("../Cargo.toml").unwrap_err();
+        assert!(matches!(err, Error::FailedToParse));
+    }
 }
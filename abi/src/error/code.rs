@@ -0,0 +1,204 @@
+// 只靠 gRPC status code 無法分辨是哪一種錯誤 (例如 InvalidTime 和 InvalidUserId 都是 INVALID_ARGUMENT)，
+// 所以每個 abi::Error 都對應一個穩定的 error code，放在 status metadata 的 "x-error-code" 裡給 client 判斷。
+// code 一旦發佈就不能改名，只能新增。
+
+use crate::Error;
+use std::fmt;
+
+// metadata key of the error code in tonic::Status
+pub const ERROR_CODE_METADATA_KEY: &str = "x-error-code";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    ReservationNotFound,
+    ConfigParseFailed,
+    ConfigReadFailed,
+    DatabaseError,
+    InvalidTime,
+    InvalidUserId,
+    InvalidReservationId,
+    ReservationConflict,
+    InvalidResourceId,
+    Unknown,
+    ConflictParseFailed,
+    QueryWindowTooLarge,
+    QueryTruncated,
+    QueryTimeout,
+    ReservationNotPending,
+    DatabaseUnavailable,
+    TransactionAborted,
+    SlotMisaligned,
+    InvalidPolicy,
+    DurationTooShort,
+    DurationTooLong,
+    StartTooSoon,
+    StartTooFarAhead,
+    OutsideOpeningHours,
+    QuotaExceeded,
+    UserReservationConflict,
+    InvalidPredicate,
+    IdempotencyKeyReused,
+    IdempotencyKeyInProgress,
+    VersionMismatch,
+    InvalidDelegation,
+    NotDelegated,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReservationNotFound => "RESERVATION_NOT_FOUND",
+            Self::ConfigParseFailed => "CONFIG_PARSE_FAILED",
+            Self::ConfigReadFailed => "CONFIG_READ_FAILED",
+            Self::DatabaseError => "DATABASE_ERROR",
+            Self::InvalidTime => "INVALID_TIME",
+            Self::InvalidUserId => "INVALID_USER_ID",
+            Self::InvalidReservationId => "INVALID_RESERVATION_ID",
+            Self::ReservationConflict => "RESERVATION_CONFLICT",
+            Self::InvalidResourceId => "INVALID_RESOURCE_ID",
+            Self::Unknown => "UNKNOWN",
+            Self::ConflictParseFailed => "CONFLICT_PARSE_FAILED",
+            Self::QueryWindowTooLarge => "QUERY_WINDOW_TOO_LARGE",
+            Self::QueryTruncated => "QUERY_TRUNCATED",
+            Self::QueryTimeout => "QUERY_TIMEOUT",
+            Self::ReservationNotPending => "RESERVATION_NOT_PENDING",
+            Self::DatabaseUnavailable => "DATABASE_UNAVAILABLE",
+            Self::TransactionAborted => "TRANSACTION_ABORTED",
+            Self::SlotMisaligned => "SLOT_MISALIGNED",
+            Self::InvalidPolicy => "INVALID_POLICY",
+            Self::DurationTooShort => "DURATION_TOO_SHORT",
+            Self::DurationTooLong => "DURATION_TOO_LONG",
+            Self::StartTooSoon => "START_TOO_SOON",
+            Self::StartTooFarAhead => "START_TOO_FAR_AHEAD",
+            Self::OutsideOpeningHours => "OUTSIDE_OPENING_HOURS",
+            Self::QuotaExceeded => "QUOTA_EXCEEDED",
+            Self::UserReservationConflict => "USER_RESERVATION_CONFLICT",
+            Self::InvalidPredicate => "INVALID_PREDICATE",
+            Self::IdempotencyKeyReused => "IDEMPOTENCY_KEY_REUSED",
+            Self::IdempotencyKeyInProgress => "IDEMPOTENCY_KEY_IN_PROGRESS",
+            Self::VersionMismatch => "VERSION_MISMATCH",
+            Self::InvalidDelegation => "INVALID_DELEGATION",
+            Self::NotDelegated => "NOT_DELEGATED",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Error {
+    // no wildcard here, so a new variant must choose its code
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound => ErrorCode::ReservationNotFound,
+            Self::FailedToParse => ErrorCode::ConfigParseFailed,
+            Self::FailedToRead => ErrorCode::ConfigReadFailed,
+            Self::DbError(_) => ErrorCode::DatabaseError,
+            Self::InvalidTime => ErrorCode::InvalidTime,
+            Self::InvalidUserId(_) => ErrorCode::InvalidUserId,
+            Self::InvalidReservationId(_) => ErrorCode::InvalidReservationId,
+            Self::ConflictReservation(_) => ErrorCode::ReservationConflict,
+            Self::InvalidResourceId(_) => ErrorCode::InvalidResourceId,
+            Self::Unknown => ErrorCode::Unknown,
+            Self::ParsedFailed => ErrorCode::ConflictParseFailed,
+            Self::QueryWindowTooLarge(_) => ErrorCode::QueryWindowTooLarge,
+            Self::QueryTruncated(_) => ErrorCode::QueryTruncated,
+            Self::QueryTimeout => ErrorCode::QueryTimeout,
+            Self::NotPending(_) => ErrorCode::ReservationNotPending,
+            Self::DbUnavailable(_) => ErrorCode::DatabaseUnavailable,
+            Self::DbAborted(_) => ErrorCode::TransactionAborted,
+            Self::SlotMisaligned(_) => ErrorCode::SlotMisaligned,
+            Self::InvalidPolicy(_) => ErrorCode::InvalidPolicy,
+            Self::DurationTooShort(_) => ErrorCode::DurationTooShort,
+            Self::DurationTooLong(_) => ErrorCode::DurationTooLong,
+            Self::StartTooSoon(_) => ErrorCode::StartTooSoon,
+            Self::StartTooFarAhead(_) => ErrorCode::StartTooFarAhead,
+            Self::OutsideOpeningHours => ErrorCode::OutsideOpeningHours,
+            Self::QuotaExceeded(_) => ErrorCode::QuotaExceeded,
+            Self::UserConflictReservation(_) => ErrorCode::UserReservationConflict,
+            Self::InvalidPredicate(_) => ErrorCode::InvalidPredicate,
+            Self::IdempotencyKeyReused(_) => ErrorCode::IdempotencyKeyReused,
+            Self::IdempotencyKeyInProgress(_) => ErrorCode::IdempotencyKeyInProgress,
+            Self::VersionMismatch(_) => ErrorCode::VersionMismatch,
+            Self::InvalidDelegation(_) => ErrorCode::InvalidDelegation,
+            Self::NotDelegated(_) => ErrorCode::NotDelegated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_should_carry_error_code() {
+        let status = tonic::Status::from(Error::NotFound);
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(
+            status.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(),
+            "RESERVATION_NOT_FOUND"
+        );
+
+        // same grpc code, different error code
+        let time = tonic::Status::from(Error::InvalidTime);
+        let user = tonic::Status::from(Error::InvalidUserId("".to_string()));
+        assert_eq!(time.code(), user.code());
+        assert_ne!(
+            time.metadata().get(ERROR_CODE_METADATA_KEY),
+            user.metadata().get(ERROR_CODE_METADATA_KEY)
+        );
+    }
+
+    #[test]
+    fn version_mismatch_should_send_back_the_current_reservation() {
+        let current = crate::Reservation {
+            id: 1,
+            version: 3,
+            ..Default::default()
+        };
+        let status = tonic::Status::from(Error::VersionMismatch(Box::new(current.clone())));
+        assert_eq!(status.code(), tonic::Code::Aborted);
+        assert_eq!(
+            crate::Reservation::from_version_mismatch(&status),
+            Some(current)
+        );
+        // aborted, but retrying the same request won't help
+        assert!(status
+            .metadata()
+            .get(crate::error::RETRY_PUSHBACK_METADATA_KEY)
+            .is_none());
+
+        let status = tonic::Status::from(Error::NotFound);
+        assert_eq!(crate::Reservation::from_version_mismatch(&status), None);
+    }
+
+    #[test]
+    fn transient_db_error_should_be_retryable_status() {
+        let err = Error::from(sqlx::Error::PoolTimedOut);
+        assert!(err.is_transient());
+
+        let status = tonic::Status::from(err);
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert_eq!(
+            status.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(),
+            "DATABASE_UNAVAILABLE"
+        );
+        assert!(status
+            .metadata()
+            .get(crate::error::RETRY_PUSHBACK_METADATA_KEY)
+            .is_some());
+
+        // permanent error should not carry retry hint
+        let err = Error::from(sqlx::Error::ColumnNotFound("id".to_string()));
+        assert!(!err.is_transient());
+        let status = tonic::Status::from(err);
+        assert_eq!(status.code(), tonic::Code::Internal);
+        assert!(status
+            .metadata()
+            .get(crate::error::RETRY_PUSHBACK_METADATA_KEY)
+            .is_none());
+    }
+}
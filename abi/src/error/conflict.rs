@@ -7,12 +7,13 @@ test manager::tests::reserve_conflict_should_reject ... ok
 
 // 那由於PgDatabaseError也沒有其他方法提供一個更好的錯誤信息，我們只能自己implement function從 PgDatabaseError中的 "get_raw()"取得一大坨原始資料並解析它。
 
-// // TODO: write a parser
+// 原本用 regex 解析，但 resource id 只能是 [a-zA-Z0-9_-]，也處理不了 infinity 或 (/] 的 bound。
+// 現在改成依照 "(resource_id, timespan)=(" 這個不會被翻譯的欄位名稱切開，再手動解析 tstzrange literal，
+// resource id 可以是任何字元，真的解析不了 (例如訊息格式不同) 就交給 manager 直接查衝突的那筆資料。
 
 use crate::Error;
 use chrono::{DateTime, Utc};
-use regex::Regex;
-use std::{collections::HashMap, convert::Infallible, str::FromStr, vec};
+use std::{convert::Infallible, str::FromStr};
 
 //? target: to parse the info into below struct
 
@@ -48,9 +49,12 @@ impl FromStr for ReservationConflict {
     }
 }
 
+// column names are not localized, so they are used to find the key values
+const KEY_MARKER: &str = "(resource_id, timespan)=(";
+
 pub struct ParsedInfo {
-    new: HashMap<String, String>,
-    old: HashMap<String, String>,
+    new: (String, RangeLiteral),
+    old: (String, RangeLiteral),
 }
 
 impl FromStr for ParsedInfo {
@@ -62,43 +66,14 @@ impl FromStr for ParsedInfo {
      */
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // use regex to parse the string
-        // TODO: how to write regular expressions
-        let regex = Regex::new(r"\((?P<k1>[a-zA-Z0-9_-]+)\s*,\s*(?P<k2>[a-zA-Z0-9_-]+)\)=\((?P<v1>[a-zA-Z0-9_-]+)\s*,\s*\[(?P<v2>[^\)\]]+)").unwrap();
-
-        // let maps = regex
-        //     .captures_iter(s)
-        //     .map(|cap| {
-        //         let mut map = HashMap::new();
-        //         map.insert(cap["k1"].to_string(), cap["v1"].to_string());
-        //         map.insert(cap["k2"].to_string(), cap["v2"].to_string());
-        //         map
-        //     })
-        //     .collect::<Vec<HashMap<String, String>>>();
-
-        // // TODO: how can I convert vec into result
-
-        let result_parsed_info = regex
-            .captures_iter(s)
-            .try_fold(vec![], |mut vec_maps, cap_item| {
-                let mut item = HashMap::new();
-                item.insert(cap_item["k1"].to_string(), cap_item["v1"].to_string());
-                item.insert(cap_item["k2"].to_string(), cap_item["v2"].to_string());
-                vec_maps.push(Some(item));
-                Ok(vec_maps)
-            })
-            .and_then(|mut collected_maps| {
-                if collected_maps.len() == 2 {
-                    Ok(ParsedInfo {
-                        new: collected_maps[0].take().unwrap(), // //! 這邊會使用take()來取出item並把vec_maps中的item設為None
-                        old: collected_maps[1].take().unwrap(), // //! 原本使用clone()，但複製HashMap是不必要的，所以改用take()
-                    })
-                } else {
-                    Err(Error::ParsedFailed)
-                }
-            });
-
-        result_parsed_info
+        let mut parts = s.split(KEY_MARKER).skip(1);
+        let new = parse_key_value(parts.next().ok_or(Error::ParsedFailed)?)?;
+        let old = parse_key_value(parts.next().ok_or(Error::ParsedFailed)?)?;
+        if parts.next().is_some() {
+            return Err(Error::ParsedFailed);
+        }
+
+        Ok(ParsedInfo { new, old })
     }
 }
 
@@ -113,41 +88,88 @@ impl TryFrom<ParsedInfo> for ReservationConflict {
     }
 }
 
+// start or end is None if the bound is unbounded or infinity
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReservationWindow {
     pub rid: String,
-    pub start: DateTime<Utc>,
-    pub end: DateTime<Utc>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
 }
 
-impl TryFrom<HashMap<String, String>> for ReservationWindow {
+impl TryFrom<(String, RangeLiteral)> for ReservationWindow {
     type Error = Error;
 
-    fn try_from(map: HashMap<String, String>) -> Result<Self, Self::Error> {
-        let replaced_map = map
-            .get("timespan")
-            .ok_or(Error::ParsedFailed)?
-            .replace('"', ""); //? 把timespan中的"去掉
+    fn try_from((rid, range): (String, RangeLiteral)) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rid,
+            start: parse_bound(range.lower.as_deref())?,
+            end: parse_bound(range.upper.as_deref())?,
+        })
+    }
+}
 
-        let mut split = replaced_map.splitn(2, ',');
+// raw text of a tstzrange literal, e.g. ["2022-12-26 22:00:00+00",) has upper None.
+// inclusive/exclusive doesn't matter for a window, so the bound kinds are only checked, not kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeLiteral {
+    lower: Option<String>,
+    upper: Option<String>,
+}
 
-        let start = parse_time_into_utc(split.next().ok_or(Error::ParsedFailed)?)?;
+// "<resource_id>, <range literal>)..." - resource id can contain anything (even ", "), so try every ", "
+// until the rest is a valid range literal closed by the key parenthesis
+fn parse_key_value(s: &str) -> Result<(String, RangeLiteral), Error> {
+    s.match_indices(", ")
+        .find_map(|(idx, sep)| {
+            let (range, rest) = parse_range_literal(&s[idx + sep.len()..])?;
+            rest.starts_with(')').then(|| (s[..idx].to_string(), range))
+        })
+        .ok_or(Error::ParsedFailed)
+}
 
-        let end = parse_time_into_utc(split.next().ok_or(Error::ParsedFailed)?)?;
+// returns the range and the rest of the string
+fn parse_range_literal(s: &str) -> Option<(RangeLiteral, &str)> {
+    let s = s.strip_prefix(['[', '('])?;
+    let (lower, s) = parse_range_element(s)?;
+    let s = s.strip_prefix(',')?;
+    let (upper, s) = parse_range_element(s)?;
+    let s = s.strip_prefix([']', ')'])?;
+    Some((RangeLiteral { lower, upper }, s))
+}
 
-        Ok(Self {
-            rid: map
-                .get("resource_id")
-                .ok_or(Error::ParsedFailed)?
-                .to_string(),
-            start,
-            end,
-        })
+// element is either empty (unbounded), quoted ("" and \ escape) or unquoted text
+fn parse_range_element(s: &str) -> Option<(Option<String>, &str)> {
+    let Some(quoted) = s.strip_prefix('"') else {
+        let end = s.find([',', ']', ')'])?;
+        let value = &s[..end];
+        return Some(((!value.is_empty()).then(|| value.to_string()), &s[end..]));
+    };
+
+    let mut value = String::new();
+    let mut chars = quoted.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?.1),
+            '"' if matches!(chars.peek(), Some((_, '"'))) => {
+                chars.next();
+                value.push('"');
+            }
+            '"' => return Some((Some(value), &quoted[idx + 1..])),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+fn parse_bound(s: Option<&str>) -> Result<Option<DateTime<Utc>>, Error> {
+    match s {
+        None | Some("infinity") | Some("-infinity") => Ok(None),
+        Some(s) => parse_time_into_utc(s).map(Some),
     }
 }
 
 fn parse_time_into_utc(s: &str) -> Result<DateTime<Utc>, Error> {
-    Ok(DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%#z")
+    Ok(DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f%#z")
         .map_err(|_| Error::InvalidTime)?
         .with_timezone(&Utc))
 }
@@ -162,35 +184,50 @@ mod tests {
     fn test_parse_function_to_take_datetime_utc() {
         let dt = parse_time_into_utc("2022-12-26 22:00:00+00").unwrap();
         assert_eq!(dt.to_rfc3339(), "2022-12-26T22:00:00+00:00");
+
+        let dt = parse_time_into_utc("2022-12-26 22:00:00.5+05:30").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2022-12-26T16:30:00.500+00:00");
     }
 
     #[test]
     fn parsed_info_should_work() {
         let info: ParsedInfo = ERR_MSG.parse().unwrap();
-        assert_eq!(info.new["resource_id"], "ocean-view-room-713");
+        assert_eq!(info.new.0, "ocean-view-room-713");
         assert_eq!(
-            info.new["timespan"],
-            "\"2022-12-26 22:00:00+00\",\"2022-12-30 19:00:00+00\""
+            info.new.1,
+            RangeLiteral {
+                lower: Some("2022-12-26 22:00:00+00".to_string()),
+                upper: Some("2022-12-30 19:00:00+00".to_string()),
+            }
         );
-        assert_eq!(info.old["resource_id"], "ocean-view-room-713");
+        assert_eq!(info.old.0, "ocean-view-room-713");
         assert_eq!(
-            info.old["timespan"],
-            "\"2022-12-25 22:00:00+00\",\"2022-12-28 19:00:00+00\""
+            info.old.1,
+            RangeLiteral {
+                lower: Some("2022-12-25 22:00:00+00".to_string()),
+                upper: Some("2022-12-28 19:00:00+00".to_string()),
+            }
         );
     }
 
     #[test]
-    fn hash_map_to_reservation_window_should_work() {
-        let mut map = HashMap::new();
-        map.insert("resource_id".to_string(), "ocean-view-room-713".to_string());
-        map.insert(
-            "timespan".to_string(),
-            "\"2022-12-26 22:00:00+00\",\"2022-12-30 19:00:00+00\"".to_string(),
-        );
-        let window: ReservationWindow = map.try_into().unwrap();
+    fn range_literal_to_reservation_window_should_work() {
+        let range = RangeLiteral {
+            lower: Some("2022-12-26 22:00:00+00".to_string()),
+            upper: Some("2022-12-30 19:00:00+00".to_string()),
+        };
+        let window: ReservationWindow = ("ocean-view-room-713".to_string(), range)
+            .try_into()
+            .unwrap();
         assert_eq!(window.rid, "ocean-view-room-713");
-        assert_eq!(window.start.to_rfc3339(), "2022-12-26T22:00:00+00:00");
-        assert_eq!(window.end.to_rfc3339(), "2022-12-30T19:00:00+00:00");
+        assert_eq!(
+            window.start.unwrap().to_rfc3339(),
+            "2022-12-26T22:00:00+00:00"
+        );
+        assert_eq!(
+            window.end.unwrap().to_rfc3339(),
+            "2022-12-30T19:00:00+00:00"
+        );
     }
 
     #[test]
@@ -199,13 +236,62 @@ mod tests {
         match info {
             ReservationConflictInfo::Parsed(conflict) => {
                 assert_eq!(conflict.new.rid, "ocean-view-room-713");
-                assert_eq!(conflict.new.start.to_rfc3339(), "2022-12-26T22:00:00+00:00");
-                assert_eq!(conflict.new.end.to_rfc3339(), "2022-12-30T19:00:00+00:00");
+                assert_eq!(
+                    conflict.new.start.unwrap().to_rfc3339(),
+                    "2022-12-26T22:00:00+00:00"
+                );
+                assert_eq!(
+                    conflict.new.end.unwrap().to_rfc3339(),
+                    "2022-12-30T19:00:00+00:00"
+                );
                 assert_eq!(conflict.old.rid, "ocean-view-room-713");
-                assert_eq!(conflict.old.start.to_rfc3339(), "2022-12-25T22:00:00+00:00");
-                assert_eq!(conflict.old.end.to_rfc3339(), "2022-12-28T19:00:00+00:00");
+                assert_eq!(
+                    conflict.old.start.unwrap().to_rfc3339(),
+                    "2022-12-25T22:00:00+00:00"
+                );
+                assert_eq!(
+                    conflict.old.end.unwrap().to_rfc3339(),
+                    "2022-12-28T19:00:00+00:00"
+                );
             }
             ReservationConflictInfo::Unparsed(_) => panic!("should be parsed"),
         }
     }
+
+    #[test]
+    fn conflict_with_any_resource_id_should_parse() {
+        for rid in ["room 7.1", "bldg:3/room, A", "會議室-一", "a)=(b, [c"] {
+            let msg = format!(
+                "Key (resource_id, timespan)=({rid}, [\"2022-12-26 22:00:00+00\",\"2022-12-30 19:00:00+00\")) conflicts with existing key (resource_id, timespan)=({rid}, [\"2022-12-25 22:00:00+00\",\"2022-12-28 19:00:00+00\"))."
+            );
+            let conflict: ReservationConflict = msg.parse().unwrap();
+            assert_eq!(conflict.new.rid, rid);
+            assert_eq!(conflict.old.rid, rid);
+        }
+    }
+
+    #[test]
+    fn conflict_with_unbounded_and_other_bound_kinds_should_parse() {
+        // localized message, infinite and unbounded bounds, (] bound kinds
+        let msg = "鍵 (resource_id, timespan)=(desk-1, (\"2022-12-26 22:00:00.25+08\",infinity]) 與已存在的鍵 (resource_id, timespan)=(desk-1, [\"2022-12-25 22:00:00+00\",)) 衝突";
+        let conflict: ReservationConflict = msg.parse().unwrap();
+        assert_eq!(
+            conflict.new.start.unwrap().to_rfc3339(),
+            "2022-12-26T14:00:00.250+00:00"
+        );
+        assert_eq!(conflict.new.end, None);
+        assert_eq!(
+            conflict.old.start.unwrap().to_rfc3339(),
+            "2022-12-25T22:00:00+00:00"
+        );
+        assert_eq!(conflict.old.end, None);
+    }
+
+    #[test]
+    fn unknown_message_should_be_unparsed() {
+        let info: ReservationConflictInfo = "conflicting key value violates exclusion constraint"
+            .parse()
+            .unwrap();
+        assert!(matches!(info, ReservationConflictInfo::Unparsed(_)));
+    }
 }
@@ -1,19 +1,24 @@
+mod code;
 mod conflict;
+mod quota;
 
+use crate::{Reservation, ReservationStatus, StatusDetail};
 use sqlx::postgres::PgDatabaseError;
 use thiserror::Error;
 
+pub use code::{ErrorCode, ERROR_CODE_METADATA_KEY};
 pub use conflict::{ReservationConflict, ReservationConflictInfo, ReservationWindow};
+pub use quota::QuotaUsage;
 
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("pending was confirmed")]
+    #[error("reservation not found")]
     NotFound,
 
     #[error("failed to parse config file")]
     FailedToParse,
 
-    #[error("failed to read file")]
+    #[error("failed to read config file")]
     FailedToRead,
 
     #[error("database error")]
@@ -37,23 +42,138 @@ pub enum Error {
     #[error("unknown error")]
     Unknown,
 
-    #[error("parsed failed")]
+    #[error("failed to parse conflict info")]
     ParsedFailed,
+
+    #[error("query window is longer than {0} days")]
+    QueryWindowTooLarge(i64),
+
+    #[error("query result is truncated at {0} rows")]
+    QueryTruncated(i64),
+
+    #[error("query timeout")]
+    QueryTimeout,
+
+    #[error("reservation is {0}, only pending reservation can be confirmed")]
+    NotPending(ReservationStatus),
+
+    #[error("database unavailable")]
+    DbUnavailable(sqlx::Error),
+
+    #[error("database transaction aborted")]
+    DbAborted(sqlx::Error),
+
+    #[error("reservation time is not aligned to {0} minutes slot")]
+    SlotMisaligned(i32),
+
+    #[error("invalid resource policy: {0}")]
+    InvalidPolicy(String),
+
+    #[error("reservation is shorter than {0} minutes")]
+    DurationTooShort(i32),
+
+    #[error("reservation is longer than {0} minutes")]
+    DurationTooLong(i32),
+
+    #[error("reservation must start at least {0} minutes from now")]
+    StartTooSoon(i32),
+
+    #[error("reservation must start within {0} minutes from now")]
+    StartTooFarAhead(i32),
+
+    #[error("reservation is outside the opening hours")]
+    OutsideOpeningHours,
+
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(QuotaUsage),
+
+    #[error("user already has a reservation at the same time")]
+    UserConflictReservation(ReservationConflictInfo),
+
+    #[error("invalid attribute predicate: {0}")]
+    InvalidPredicate(String),
+
+    #[error("idempotency key {0} is already used by a different request")]
+    IdempotencyKeyReused(String),
+
+    #[error("request with idempotency key {0} is still in progress")]
+    IdempotencyKeyInProgress(String),
+
+    #[error("reservation is changed by others, current version is {}", .0.version)]
+    VersionMismatch(Box<Reservation>),
+
+    #[error("invalid delegation: {0}")]
+    InvalidDelegation(String),
+
+    #[error("not delegated: {0}")]
+    NotDelegated(String),
+}
+
+// retry hint for transient errors, see https://github.com/grpc/proposal/blob/master/A6-client-retries.md
+pub const RETRY_PUSHBACK_METADATA_KEY: &str = "grpc-retry-pushback-ms";
+const UNAVAILABLE_RETRY_AFTER_MS: &str = "1000";
+const ABORTED_RETRY_AFTER_MS: &str = "100";
+
+impl Error {
+    // transient errors are worth retrying by the client, the request may succeed next time
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::DbUnavailable(_) | Self::DbAborted(_))
+    }
 }
 
 impl From<sqlx::Error> for Error {
     fn from(e: sqlx::Error) -> Self {
         match e {
             sqlx::Error::Database(e) => {
-                let err: &PgDatabaseError = e.downcast_ref();
+                let Some(err) = e.try_downcast_ref::<PgDatabaseError>() else {
+                    return Error::DbError(sqlx::Error::Database(e));
+                };
                 match (err.code(), err.schema(), err.table()) {
+                    // the same user on another resource, detail is not parsed here, manager looks up the row instead
+                    ("23P01", Some("rsvp"), Some("reservations"))
+                        if err.constraint() == Some("reservations_user_conflict") =>
+                    {
+                        Error::UserConflictReservation(ReservationConflictInfo::Unparsed(
+                            err.detail().unwrap_or(err.message()).to_string(),
+                        ))
+                    }
                     ("23P01", Some("rsvp"), Some("reservations")) => {
-                        Error::ConflictReservation(err.detail().unwrap().parse().unwrap())
+                        // parsing ReservationConflictInfo never fails, fallback to Unparsed
+                        let info = match err.detail() {
+                            Some(detail) => detail.parse(),
+                            None => {
+                                Ok(ReservationConflictInfo::Unparsed(err.message().to_string()))
+                            }
+                        };
+                        match info {
+                            Ok(info) => Error::ConflictReservation(info),
+                            Err(never) => match never {},
+                        }
+                    }
+                    // overlapping with a resource group or one of its members, the detail only has the member
+                    ("23P01", Some("rsvp"), Some("reservation_claims")) => {
+                        Error::ConflictReservation(ReservationConflictInfo::Unparsed(
+                            err.detail().unwrap_or(err.message()).to_string(),
+                        ))
+                    }
+                    // query_canceled, raised when statement_timeout is reached
+                    ("57014", _, _) => Error::QueryTimeout,
+                    // serialization_failure, deadlock_detected: the transaction is rolled back and can be retried
+                    ("40001" | "40P01", _, _) => Error::DbAborted(sqlx::Error::Database(e)),
+                    // connection_exception (08xxx), too_many_connections, admin/crash shutdown, cannot_connect_now
+                    (code, _, _)
+                        if code.starts_with("08")
+                            || matches!(code, "53300" | "57P01" | "57P02" | "57P03") =>
+                    {
+                        Error::DbUnavailable(sqlx::Error::Database(e))
                     }
                     _ => Error::DbError(sqlx::Error::Database(e)),
                 }
             }
             sqlx::Error::RowNotFound => Error::NotFound,
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => {
+                Error::DbUnavailable(e)
+            }
             _ => Error::DbError(e),
         }
     }
@@ -71,6 +191,27 @@ impl PartialEq for Error {
             (Self::Unknown, Self::Unknown) => true,
             (Self::ParsedFailed, Self::ParsedFailed) => true,
             (Self::NotFound, Self::NotFound) => true,
+            (Self::QueryWindowTooLarge(v1), Self::QueryWindowTooLarge(v2)) => v1 == v2,
+            (Self::QueryTruncated(v1), Self::QueryTruncated(v2)) => v1 == v2,
+            (Self::QueryTimeout, Self::QueryTimeout) => true,
+            (Self::NotPending(v1), Self::NotPending(v2)) => v1 == v2,
+            (Self::DbUnavailable(_), Self::DbUnavailable(_)) => true,
+            (Self::DbAborted(_), Self::DbAborted(_)) => true,
+            (Self::SlotMisaligned(v1), Self::SlotMisaligned(v2)) => v1 == v2,
+            (Self::InvalidPolicy(v1), Self::InvalidPolicy(v2)) => v1 == v2,
+            (Self::DurationTooShort(v1), Self::DurationTooShort(v2)) => v1 == v2,
+            (Self::DurationTooLong(v1), Self::DurationTooLong(v2)) => v1 == v2,
+            (Self::StartTooSoon(v1), Self::StartTooSoon(v2)) => v1 == v2,
+            (Self::StartTooFarAhead(v1), Self::StartTooFarAhead(v2)) => v1 == v2,
+            (Self::OutsideOpeningHours, Self::OutsideOpeningHours) => true,
+            (Self::QuotaExceeded(v1), Self::QuotaExceeded(v2)) => v1 == v2,
+            (Self::UserConflictReservation(v1), Self::UserConflictReservation(v2)) => v1 == v2,
+            (Self::InvalidPredicate(v1), Self::InvalidPredicate(v2)) => v1 == v2,
+            (Self::IdempotencyKeyReused(v1), Self::IdempotencyKeyReused(v2)) => v1 == v2,
+            (Self::IdempotencyKeyInProgress(v1), Self::IdempotencyKeyInProgress(v2)) => v1 == v2,
+            (Self::VersionMismatch(v1), Self::VersionMismatch(v2)) => v1 == v2,
+            (Self::InvalidDelegation(v1), Self::InvalidDelegation(v2)) => v1 == v2,
+            (Self::NotDelegated(v1), Self::NotDelegated(v2)) => v1 == v2,
             _ => false,
         }
     }
@@ -78,7 +219,8 @@ impl PartialEq for Error {
 
 impl From<crate::Error> for tonic::Status {
     fn from(e: crate::Error) -> Self {
-        match e {
+        let code = e.code();
+        let mut status = match e {
             crate::Error::NotFound => tonic::Status::not_found("not found"),
             crate::Error::InvalidTime => tonic::Status::invalid_argument("invalid time"),
             crate::Error::InvalidUserId(v) => {
@@ -93,11 +235,104 @@ impl From<crate::Error> for tonic::Status {
             crate::Error::InvalidResourceId(v) => {
                 tonic::Status::invalid_argument(format!("invalid resource id: {}", v))
             }
-            crate::Error::ParsedFailed => tonic::Status::unknown("parsed failed"),
-            crate::Error::FailedToParse => tonic::Status::unknown("failed to parse"),
-            crate::Error::FailedToRead => tonic::Status::unknown("failed to read"),
+            crate::Error::ParsedFailed => tonic::Status::internal("failed to parse conflict info"),
+            crate::Error::FailedToParse => tonic::Status::internal("failed to parse config file"),
+            crate::Error::FailedToRead => tonic::Status::internal("failed to read config file"),
             crate::Error::DbError(e) => tonic::Status::internal(format!("Database error: {}", e)),
             crate::Error::Unknown => tonic::Status::unknown("unknown"),
+            crate::Error::QueryWindowTooLarge(v) => tonic::Status::invalid_argument(format!(
+                "query window is longer than {} days, narrow start and end",
+                v
+            )),
+            crate::Error::QueryTruncated(v) => tonic::Status::resource_exhausted(format!(
+                "query result is truncated at {} rows, narrow the query or set a limit",
+                v
+            )),
+            crate::Error::QueryTimeout => tonic::Status::deadline_exceeded("query timeout"),
+            crate::Error::NotPending(v) => tonic::Status::failed_precondition(format!(
+                "reservation is {}, only pending reservation can be confirmed",
+                v
+            )),
+            crate::Error::DbUnavailable(e) => {
+                tonic::Status::unavailable(format!("Database unavailable: {}", e))
+            }
+            crate::Error::DbAborted(e) => {
+                tonic::Status::aborted(format!("Database transaction aborted: {}", e))
+            }
+            crate::Error::SlotMisaligned(v) => tonic::Status::invalid_argument(format!(
+                "reservation start and end must be aligned to {} minutes slot",
+                v
+            )),
+            crate::Error::InvalidPolicy(v) => {
+                tonic::Status::invalid_argument(format!("invalid resource policy: {}", v))
+            }
+            crate::Error::DurationTooShort(v) => tonic::Status::invalid_argument(format!(
+                "reservation must be at least {} minutes long",
+                v
+            )),
+            crate::Error::DurationTooLong(v) => tonic::Status::invalid_argument(format!(
+                "reservation must be at most {} minutes long, open-ended reservation is not allowed",
+                v
+            )),
+            crate::Error::StartTooSoon(v) => tonic::Status::invalid_argument(format!(
+                "reservation must start at least {} minutes from now",
+                v
+            )),
+            crate::Error::StartTooFarAhead(v) => tonic::Status::invalid_argument(format!(
+                "reservation can't start more than {} minutes from now",
+                v
+            )),
+            crate::Error::OutsideOpeningHours => tonic::Status::invalid_argument(
+                "reservation is outside the opening hours or on a holiday of the resource",
+            ),
+            crate::Error::QuotaExceeded(v) => {
+                tonic::Status::resource_exhausted(format!("quota exceeded: {}", v))
+            }
+            crate::Error::UserConflictReservation(v) => tonic::Status::failed_precondition(
+                format!("user already has a reservation at the same time: {:?}", v),
+            ),
+            crate::Error::InvalidPredicate(v) => {
+                tonic::Status::invalid_argument(format!("invalid attribute predicate: {}", v))
+            }
+            crate::Error::IdempotencyKeyReused(v) => tonic::Status::invalid_argument(format!(
+                "idempotency key {} is already used by a different request, use a new key",
+                v
+            )),
+            crate::Error::IdempotencyKeyInProgress(v) => tonic::Status::aborted(format!(
+                "request with idempotency key {} is still in progress, retry later",
+                v
+            )),
+            // the client should reload and merge, so the current reservation is sent back as details
+            crate::Error::VersionMismatch(v) => v.attach_to_status(tonic::Status::aborted(
+                format!(
+                    "reservation is changed by others, current version is {}",
+                    v.version
+                ),
+            )),
+            crate::Error::InvalidDelegation(v) => {
+                tonic::Status::invalid_argument(format!("invalid delegation: {}", v))
+            }
+            crate::Error::NotDelegated(v) => {
+                tonic::Status::permission_denied(format!("not delegated: {}", v))
+            }
+        };
+        let retry_after = match code {
+            ErrorCode::DatabaseUnavailable => Some(UNAVAILABLE_RETRY_AFTER_MS),
+            ErrorCode::TransactionAborted | ErrorCode::IdempotencyKeyInProgress => {
+                Some(ABORTED_RETRY_AFTER_MS)
+            }
+            _ => None,
+        };
+        if let Some(ms) = retry_after {
+            status.metadata_mut().insert(
+                RETRY_PUSHBACK_METADATA_KEY,
+                tonic::metadata::MetadataValue::from_static(ms),
+            );
         }
+        status.metadata_mut().insert(
+            ERROR_CODE_METADATA_KEY,
+            tonic::metadata::MetadataValue::from_static(code.as_str()),
+        );
+        status
     }
 }
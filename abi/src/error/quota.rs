@@ -0,0 +1,17 @@
+use std::fmt;
+
+// current usage of the quota which is exceeded, so the client can tell the user what to cancel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    // e.g. "active_reservations", "minutes_per_week", "active_reservations:meeting-room"
+    pub quota: String,
+    // already used, not including the rejected reservation
+    pub used: i64,
+    pub limit: i64,
+}
+
+impl fmt::Display for QuotaUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} used {} of {}", self.quota, self.used, self.limit)
+    }
+}
@@ -0,0 +1,96 @@
+// 所有從 client 來的資料都可能是任意值，這邊用 proptest 餵隨機輸入，確保這些路徑只會回傳 abi::Error 而不會 panic
+
+use crate::{
+    convert_time_to_utc, convert_timestamp_into_timespan_pgrange,
+    convert_timestamp_into_window_pgrange, validate_range, FilterById, Reservation,
+    ReservationConflictInfo, ReservationQuery, Validator,
+};
+use proptest::prelude::*;
+use prost_types::Timestamp;
+
+fn timestamp() -> impl Strategy<Value = Timestamp> {
+    prop_oneof![
+        // totally random, mostly out of range
+        (any::<i64>(), any::<i32>()).prop_map(|(seconds, nanos)| Timestamp { seconds, nanos }),
+        // around the valid range, with invalid nanos sometimes
+        (-10_000_000_000i64..10_000_000_000, -10i32..2_000_000_010)
+            .prop_map(|(seconds, nanos)| Timestamp { seconds, nanos }),
+    ]
+}
+
+fn reservation() -> impl Strategy<Value = Reservation> {
+    (
+        ".{0,8}",
+        ".{0,8}",
+        any::<i32>(),
+        proptest::option::of(timestamp()),
+        proptest::option::of(timestamp()),
+    )
+        .prop_map(|(user_id, resource_id, status, start, end)| Reservation {
+            id: 0,
+            user_id,
+            status,
+            resource_id,
+            start,
+            end,
+            note: String::new(),
+            ..Default::default()
+        })
+}
+
+proptest! {
+    #[test]
+    fn convert_time_to_utc_should_not_panic(ts in timestamp()) {
+        let _ = convert_time_to_utc(&ts);
+    }
+
+    #[test]
+    fn range_conversion_should_not_panic(
+        start in proptest::option::of(timestamp()),
+        end in proptest::option::of(timestamp()),
+    ) {
+        let valid = validate_range(start.as_ref(), end.as_ref()).is_ok();
+        let timespan = convert_timestamp_into_timespan_pgrange(start.as_ref(), end.as_ref());
+        // a valid range must be convertible
+        prop_assert!(!valid || timespan.is_ok());
+        let _ = convert_timestamp_into_window_pgrange(start.as_ref(), end.as_ref());
+    }
+
+    #[test]
+    fn reservation_validate_should_not_panic(rsvp in reservation()) {
+        if rsvp.validate().is_ok() {
+            prop_assert!(rsvp.get_timestamp().is_ok());
+        }
+        let _ = rsvp.get_timestamp();
+    }
+
+    #[test]
+    fn query_and_filter_validate_should_not_panic(
+        start in proptest::option::of(timestamp()),
+        end in proptest::option::of(timestamp()),
+    ) {
+        let query = ReservationQuery { start: start.clone(), end: end.clone(), ..Default::default() };
+        if query.validate().is_ok() {
+            prop_assert!(query.get_timespan().is_ok());
+        }
+        let filter = FilterById { start, end, ..Default::default() };
+        let _ = filter.validate();
+    }
+
+    #[test]
+    fn conflict_info_parse_should_not_panic(s in ".*") {
+        let _ = s.parse::<ReservationConflictInfo>();
+    }
+
+    #[test]
+    fn conflict_info_parse_should_not_panic_on_similar_message(
+        rid in "[a-zA-Z0-9_-]{0,16}",
+        start in ".{0,30}",
+        end in ".{0,30}",
+    ) {
+        let msg = format!(
+            "Key (resource_id, timespan)=({rid}, [\"{start}\",\"{end}\")) conflicts with existing key (resource_id, timespan)=({rid}, [\"{start}\",\"{end}\"))."
+        );
+        let _ = msg.parse::<ReservationConflictInfo>();
+    }
+}
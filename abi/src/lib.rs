@@ -1,11 +1,16 @@
 mod config;
 mod error;
+#[cfg(test)]
+mod fuzz_tests;
 mod pb;
 mod types;
 mod utils;
 
 pub use config::*;
-pub use error::{Error, ReservationConflict, ReservationConflictInfo, ReservationWindow};
+pub use error::{
+    Error, ErrorCode, QuotaUsage, ReservationConflict, ReservationConflictInfo, ReservationWindow,
+    ERROR_CODE_METADATA_KEY, RETRY_PUSHBACK_METADATA_KEY,
+};
 pub use pb::*;
 pub use types::*;
 pub use utils::*;
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Duration, Utc};
+
+use super::Interval;
+use crate::{Reservation, ReservationAlternatives, StatusDetail};
+
+// cut the busy intervals out of the open intervals, both must be sorted by start
+pub fn subtract_intervals(open: &[Interval], busy: &[Interval]) -> Vec<Interval> {
+    let mut free = vec![];
+    for &(mut start, end) in open {
+        for &(busy_start, busy_end) in busy {
+            if busy_end <= start || busy_start >= end {
+                continue;
+            }
+            if busy_start > start {
+                free.push((start, busy_start));
+            }
+            start = start.max(busy_end);
+        }
+        if start < end {
+            free.push((start, end));
+        }
+    }
+    free
+}
+
+// the start nearest to `start` in each free interval long enough for `duration`, nearest first
+pub fn nearest_starts(
+    free: &[Interval],
+    start: DateTime<Utc>,
+    duration: Duration,
+) -> Vec<DateTime<Utc>> {
+    let mut starts: Vec<_> = free
+        .iter()
+        .filter(|(from, to)| *to - *from >= duration)
+        .map(|(from, to)| start.clamp(*from, *to - duration))
+        .collect();
+    starts.sort_by_key(|s| ((*s - start).num_seconds().abs(), *s));
+    starts
+}
+
+impl ReservationAlternatives {
+    // for the client, None if the status has no alternatives
+    pub fn from_status(status: &tonic::Status) -> Option<Vec<Reservation>> {
+        Self::from_status_details(status).map(|v| v.alternatives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        format!("2030-01-07T{:02}:00:00+0000", hour)
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn busy_intervals_should_be_cut_out() {
+        let open = [(at(8), at(12)), (at(13), at(18))];
+        let busy = [(at(7), at(9)), (at(10), at(11)), (at(12), at(14))];
+        assert_eq!(
+            subtract_intervals(&open, &busy),
+            vec![(at(9), at(10)), (at(11), at(12)), (at(14), at(18))]
+        );
+        assert_eq!(subtract_intervals(&open, &[]), open.to_vec());
+    }
+
+    #[test]
+    fn nearest_starts_should_fit_the_duration() {
+        let free = [(at(9), at(10)), (at(11), at(12)), (at(14), at(18))];
+        // wants 10:00 - 12:00, 9 - 10 and 11 - 12 are too short
+        assert_eq!(
+            nearest_starts(&free, at(10), Duration::hours(2)),
+            vec![at(14)]
+        );
+        assert_eq!(
+            nearest_starts(&free, at(10), Duration::hours(1)),
+            vec![at(9), at(11), at(14)]
+        );
+        // inside a free interval, the start itself
+        assert_eq!(nearest_starts(&free, at(15), Duration::hours(1))[0], at(15));
+    }
+
+    #[test]
+    fn alternatives_should_round_trip_in_status_details() {
+        let rsvp = Reservation::new_pending(
+            "yangid",
+            "room-1",
+            "2030-01-07T10:00:00+0000".parse().unwrap(),
+            "2030-01-07T11:00:00+0000".parse().unwrap(),
+            "",
+        );
+        let status: tonic::Status = crate::Error::NotFound.into();
+        let status = ReservationAlternatives {
+            alternatives: vec![rsvp.clone()],
+        }
+        .attach_to_status(status);
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert!(status
+            .metadata()
+            .get(crate::ERROR_CODE_METADATA_KEY)
+            .is_some());
+        assert_eq!(
+            ReservationAlternatives::from_status(&status),
+            Some(vec![rsvp])
+        );
+        assert_eq!(
+            ReservationAlternatives::from_status(&tonic::Status::not_found("")),
+            None
+        );
+    }
+}
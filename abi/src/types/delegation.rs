@@ -0,0 +1,109 @@
+use std::fmt;
+
+use crate::{Delegation, DelegationAction, Error, Validator};
+
+impl fmt::Display for DelegationAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Book => write!(f, "book"),
+            Self::Confirm => write!(f, "confirm"),
+            Self::Cancel => write!(f, "cancel"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl DelegationAction {
+    // same names as the database's delegation_action
+    pub fn from_db(action: &str) -> Self {
+        match action {
+            "book" => Self::Book,
+            "confirm" => Self::Confirm,
+            "cancel" => Self::Cancel,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Delegation {
+    // known actions without duplicates, in the enum order
+    pub fn normalized_actions(&self) -> Vec<DelegationAction> {
+        let mut actions: Vec<DelegationAction> = self
+            .actions
+            .iter()
+            .filter_map(|a| DelegationAction::from_i32(*a))
+            .collect();
+        actions.sort();
+        actions.dedup();
+        actions
+    }
+}
+
+impl Validator for Delegation {
+    fn validate(&self) -> Result<(), Error> {
+        if self.owner_id.is_empty() {
+            return Err(Error::InvalidUserId(self.owner_id.clone()));
+        }
+        if self.delegate_id.is_empty() || self.delegate_id == self.owner_id {
+            return Err(Error::InvalidDelegation(format!(
+                "delegate must be another user, got {:?}",
+                self.delegate_id
+            )));
+        }
+        for action in &self.actions {
+            match DelegationAction::from_i32(*action) {
+                Some(DelegationAction::Unknown) | None => {
+                    return Err(Error::InvalidDelegation(format!(
+                        "unknown action {}",
+                        action
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delegation_should_be_validated() {
+        let delegation = Delegation {
+            owner_id: "bossid".to_string(),
+            delegate_id: "assistantid".to_string(),
+            actions: vec![
+                DelegationAction::Cancel as i32,
+                DelegationAction::Book as i32,
+                DelegationAction::Cancel as i32,
+            ],
+        };
+        assert!(delegation.validate().is_ok());
+        assert_eq!(
+            delegation.normalized_actions(),
+            vec![DelegationAction::Book, DelegationAction::Cancel]
+        );
+        for action in delegation.normalized_actions() {
+            assert_eq!(DelegationAction::from_db(&action.to_string()), action);
+        }
+
+        let to_self = Delegation {
+            delegate_id: "bossid".to_string(),
+            ..delegation.clone()
+        };
+        assert!(matches!(
+            to_self.validate(),
+            Err(Error::InvalidDelegation(_))
+        ));
+        let unknown = Delegation {
+            actions: vec![DelegationAction::Unknown as i32],
+            ..delegation
+        };
+        assert!(matches!(
+            unknown.validate(),
+            Err(Error::InvalidDelegation(_))
+        ));
+    }
+}
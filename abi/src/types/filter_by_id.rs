@@ -0,0 +1,10 @@
+use crate::{validate_window, Error, FilterById, Validator};
+
+impl Validator for FilterById {
+    fn validate(&self) -> Result<(), Error> {
+        // start and end are optional for filter
+        validate_window(self.start.as_ref(), self.end.as_ref())?;
+        self.created_between.validate()?;
+        self.updated_between.validate()
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    validate_range, AttributeOp, AttributePredicate, Error, FindAvailableRequest, Resource,
+    Validator,
+};
+
+impl AttributePredicate {
+    // UNKNOWN is treated as EQ
+    pub fn operator(&self) -> AttributeOp {
+        match AttributeOp::from_i32(self.op) {
+            Some(AttributeOp::Unknown) | None => AttributeOp::Eq,
+            Some(op) => op,
+        }
+    }
+
+    // the value to compare with for GT to LTE, None for the other operators
+    pub fn number(&self) -> Option<f64> {
+        match self.operator() {
+            AttributeOp::Gt | AttributeOp::Gte | AttributeOp::Lt | AttributeOp::Lte => {
+                self.value.parse().ok().filter(|v: &f64| v.is_finite())
+            }
+            _ => None,
+        }
+    }
+
+    // how far the attribute is beyond the value, relative to the value. 0 for an exact fit
+    fn margin(&self, resource: &Resource) -> f64 {
+        let expected = match self.number() {
+            Some(v) => v,
+            None => return 0.0,
+        };
+        let actual = match resource
+            .attributes
+            .get(&self.key)
+            .and_then(|v| v.parse::<f64>().ok())
+        {
+            Some(v) => v,
+            None => return 0.0,
+        };
+        (actual - expected).abs() / expected.abs().max(1.0)
+    }
+}
+
+impl Validator for AttributePredicate {
+    fn validate(&self) -> Result<(), Error> {
+        if self.key.is_empty() {
+            return Err(Error::InvalidPredicate(
+                "attribute key is required".to_string(),
+            ));
+        }
+        let numeric = matches!(
+            self.operator(),
+            AttributeOp::Gt | AttributeOp::Gte | AttributeOp::Lt | AttributeOp::Lte
+        );
+        if numeric && self.number().is_none() {
+            return Err(Error::InvalidPredicate(format!(
+                "{} must be compared with a number, got {}",
+                self.key, self.value
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Validator for FindAvailableRequest {
+    fn validate(&self) -> Result<(), Error> {
+        validate_range(self.start.as_ref(), self.end.as_ref())?;
+        self.predicates.iter().try_for_each(|p| p.validate())
+    }
+}
+
+impl FindAvailableRequest {
+    // sum of the margins over all predicates, lower is a better fit
+    pub fn fit_margin(&self, resource: &Resource) -> f64 {
+        self.predicates.iter().map(|p| p.margin(resource)).sum()
+    }
+
+    // best fit first, then by id so the order is stable
+    pub fn rank(&self, resources: &mut [Resource]) {
+        resources.sort_by(|a, b| {
+            self.fit_margin(a)
+                .total_cmp(&self.fit_margin(b))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn predicate(key: &str, op: AttributeOp, value: &str) -> AttributePredicate {
+        AttributePredicate {
+            key: key.to_string(),
+            op: op as i32,
+            value: value.to_string(),
+        }
+    }
+
+    fn room(id: &str, capacity: &str) -> Resource {
+        Resource {
+            id: id.to_string(),
+            attributes: [("capacity".to_string(), capacity.to_string())].into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn predicate_should_be_validated() {
+        assert!(predicate("capacity", AttributeOp::Gte, "8")
+            .validate()
+            .is_ok());
+        assert!(predicate("projector", AttributeOp::Unknown, "true")
+            .validate()
+            .is_ok());
+        assert!(predicate("projector", AttributeOp::Exists, "")
+            .validate()
+            .is_ok());
+
+        assert!(predicate("", AttributeOp::Eq, "true").validate().is_err());
+        assert!(predicate("capacity", AttributeOp::Gte, "many")
+            .validate()
+            .is_err());
+        assert!(predicate("capacity", AttributeOp::Lt, "NaN")
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn resources_should_be_ranked_by_fit() {
+        let request = FindAvailableRequest {
+            predicates: vec![
+                predicate("capacity", AttributeOp::Gte, "8"),
+                predicate("projector", AttributeOp::Eq, "true"),
+            ],
+            ..Default::default()
+        };
+        let mut rooms = vec![
+            room("room-c", "20"),
+            room("room-b", "8"),
+            room("room-d", "10"),
+            room("room-a", "8"),
+        ];
+        request.rank(&mut rooms);
+        let ids: Vec<_> = rooms.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["room-a", "room-b", "room-d", "room-c"]);
+        assert_eq!(request.fit_margin(&rooms[2]), 0.25);
+    }
+}
@@ -0,0 +1,101 @@
+use prost::Message;
+
+use crate::{
+    CancelRequest, CancelResponse, ConfirmRequest, ConfirmResponse, Error, RescheduleRequest,
+    RescheduleResponse, ReserveRequest, ReserveResponse, UpdateRequest, UpdateResponse,
+};
+
+// a mutating request which can carry an idempotency key, the response is stored with the key
+// and replayed when the same request comes again
+pub trait Idempotent: Message + Clone + Sized {
+    type Response: Message + Default;
+
+    // keys are scoped by method, the same key on reserve and cancel are different keys
+    const METHOD: &'static str;
+
+    fn idempotency_key(&self) -> &str;
+
+    fn clear_idempotency_key(&mut self);
+
+    // the request without its key, a replay must have exactly the same fingerprint
+    fn fingerprint(&self) -> Vec<u8> {
+        let mut request = self.clone();
+        request.clear_idempotency_key();
+        request.encode_to_vec()
+    }
+
+    fn encode_response(response: &Self::Response) -> Vec<u8> {
+        response.encode_to_vec()
+    }
+
+    // stored responses are written by encode_response, decoding fails only if the message changed incompatibly
+    fn decode_response(bytes: &[u8]) -> Result<Self::Response, Error> {
+        Self::Response::decode(bytes).map_err(|_| Error::Unknown)
+    }
+}
+
+macro_rules! impl_idempotent {
+    ($($name:ident => $response:ident, $method:literal);* $(;)?) => {
+        $(
+            impl Idempotent for $name {
+                type Response = $response;
+                const METHOD: &'static str = $method;
+
+                fn idempotency_key(&self) -> &str {
+                    &self.idempotency_key
+                }
+
+                fn clear_idempotency_key(&mut self) {
+                    self.idempotency_key.clear();
+                }
+            }
+        )*
+    };
+}
+
+impl_idempotent!(
+    ReserveRequest => ReserveResponse, "reserve";
+    ConfirmRequest => ConfirmResponse, "confirm";
+    UpdateRequest => UpdateResponse, "update";
+    RescheduleRequest => RescheduleResponse, "reschedule";
+    CancelRequest => CancelResponse, "cancel";
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reservation;
+
+    #[test]
+    fn fingerprint_should_ignore_the_key_only() {
+        let rsvp = Reservation::new_pending(
+            "yangid",
+            "room-1",
+            "2030-01-07T10:00:00+0000".parse().unwrap(),
+            "2030-01-07T11:00:00+0000".parse().unwrap(),
+            "",
+        );
+        let request = ReserveRequest {
+            idempotency_key: "key-1".to_string(),
+            ..ReserveRequest::new(rsvp.clone())
+        };
+        let retry = ReserveRequest {
+            idempotency_key: "key-2".to_string(),
+            ..request.clone()
+        };
+        assert_eq!(request.fingerprint(), retry.fingerprint());
+        assert_eq!(request.idempotency_key(), "key-1");
+
+        let other = ReserveRequest {
+            suggest_alternatives: true,
+            ..request.clone()
+        };
+        assert_ne!(request.fingerprint(), other.fingerprint());
+
+        let response = ReserveResponse {
+            reservation: Some(rsvp),
+        };
+        let bytes = ReserveRequest::encode_response(&response);
+        assert_eq!(ReserveRequest::decode_response(&bytes).unwrap(), response);
+    }
+}
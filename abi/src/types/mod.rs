@@ -1,22 +1,45 @@
 use chrono::{DateTime, Utc};
 use prost_types::Timestamp;
 use sqlx::postgres::types::PgRange;
+use std::ops::Bound;
 
 use crate::{utils::convert_time_to_utc, Error};
 
+mod alternatives;
+mod delegation;
+mod filter_by_id;
+mod find_available;
+mod idempotency;
+mod opening_hours;
 mod request;
 mod reservation;
 mod reservation_query;
 mod reservation_status;
+mod resource;
+mod resource_group;
+mod resource_policy;
+mod status_details;
+mod time_window;
+mod waitlist;
+
+pub use alternatives::{nearest_starts, subtract_intervals};
+pub use idempotency::Idempotent;
+pub use status_details::StatusDetail;
+
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+// [start, end) of a period
+pub type Interval = (DateTime<Utc>, DateTime<Utc>);
 
 pub fn validate_range(start: Option<&Timestamp>, end: Option<&Timestamp>) -> Result<(), Error> {
     // check the timestamp, can not be empty
-    if start.is_none() || end.is_none() {
+    let (Some(start), Some(end)) = (start, end) else {
         return Err(Error::InvalidTime);
-    }
+    };
 
-    let start = start.as_ref().unwrap();
-    let end = end.as_ref().unwrap();
+    // make sure both can be converted into DateTime, otherwise the conversion later will fail
+    convert_time_to_utc(start)?;
+    convert_time_to_utc(end)?;
 
     // Timestamp中的結構問題，如果是start.seconds > end.seconds，那麼就是時間錯誤。但由於有可能出現秒數一樣的狀況，所以要再加上start.nanos >= end.nanos
     if start.seconds > end.seconds || (start.seconds == end.seconds && start.nanos >= end.nanos) {
@@ -25,15 +48,43 @@ pub fn validate_range(start: Option<&Timestamp>, end: Option<&Timestamp>) -> Res
     Ok(())
 }
 
+// start is required for a reservation, but end can be empty for an open-ended reservation
 pub fn convert_timestamp_into_timespan_pgrange(
     start: Option<&Timestamp>,
     end: Option<&Timestamp>,
-) -> PgRange<DateTime<Utc>> {
-    let start = convert_time_to_utc(start.as_ref().unwrap());
-    let end = convert_time_to_utc(end.as_ref().unwrap());
-    PgRange {
-        start: std::ops::Bound::Included(start),
-        end: std::ops::Bound::Excluded(end),
+) -> Result<PgRange<DateTime<Utc>>, Error> {
+    let start = convert_time_to_utc(start.ok_or(Error::InvalidTime)?)?;
+    let end = match end {
+        Some(end) => Bound::Excluded(convert_time_to_utc(end)?),
+        None => Bound::Unbounded,
+    };
+    Ok(PgRange {
+        start: Bound::Included(start),
+        end,
+    })
+}
+
+// used by query/filter windows, a missing start or end means the window is unbounded on that side
+pub fn convert_timestamp_into_window_pgrange(
+    start: Option<&Timestamp>,
+    end: Option<&Timestamp>,
+) -> Result<PgRange<DateTime<Utc>>, Error> {
+    let start = match start {
+        Some(ts) => Bound::Included(convert_time_to_utc(ts)?),
+        None => Bound::Unbounded,
+    };
+    let end = match end {
+        Some(ts) => Bound::Excluded(convert_time_to_utc(ts)?),
+        None => Bound::Unbounded,
+    };
+    Ok(PgRange { start, end })
+}
+
+// optional window used by query and filter, only check the order when both are given
+pub fn validate_window(start: Option<&Timestamp>, end: Option<&Timestamp>) -> Result<(), Error> {
+    match (start, end) {
+        (Some(_), Some(_)) => validate_range(start, end),
+        _ => convert_timestamp_into_window_pgrange(start, end).map(|_| ()),
     }
 }
 
@@ -84,6 +135,24 @@ mod tests {
         assert!(validate_range(Some(&start), Some(&end)).is_err());
     }
 
+    #[test]
+    fn test_convert_timestamp_into_window_pgrange() {
+        let end = Timestamp {
+            seconds: 1,
+            nanos: 0,
+        };
+        let range = convert_timestamp_into_window_pgrange(None, Some(&end)).unwrap();
+        assert_eq!(range.start, Bound::Unbounded);
+        assert_eq!(
+            range.end,
+            Bound::Excluded(convert_time_to_utc(&end).unwrap())
+        );
+
+        let range = convert_timestamp_into_window_pgrange(None, None).unwrap();
+        assert_eq!(range.start, Bound::Unbounded);
+        assert_eq!(range.end, Bound::Unbounded);
+    }
+
     #[test]
     fn test_convert_timestamp_into_timespan_pgrange() {
         let start = Timestamp {
@@ -94,14 +163,19 @@ mod tests {
             seconds: 1,
             nanos: 0,
         };
-        let range = convert_timestamp_into_timespan_pgrange(Some(&start), Some(&end));
+        let range = convert_timestamp_into_timespan_pgrange(Some(&start), Some(&end)).unwrap();
         assert_eq!(
             range.start,
-            std::ops::Bound::Included(convert_time_to_utc(&start))
+            std::ops::Bound::Included(convert_time_to_utc(&start).unwrap())
         );
         assert_eq!(
             range.end,
-            std::ops::Bound::Excluded(convert_time_to_utc(&end))
+            std::ops::Bound::Excluded(convert_time_to_utc(&end).unwrap())
         );
+
+        // open-ended reservation
+        let range = convert_timestamp_into_timespan_pgrange(Some(&start), None).unwrap();
+        assert_eq!(range.end, std::ops::Bound::Unbounded);
+        assert!(convert_timestamp_into_timespan_pgrange(None, Some(&end)).is_err());
     }
 }
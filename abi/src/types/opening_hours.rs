@@ -0,0 +1,450 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use sqlx::{postgres::PgRow, FromRow, Row};
+
+use super::{Interval, MINUTES_PER_DAY};
+use crate::{
+    convert_time_to_utc, Error, Holiday, HolidayCalendar, OpeningHours, Reservation,
+    ResourcePolicy, Validator,
+};
+
+const HOLIDAY_DATE_FORMAT: &str = "%Y-%m-%d";
+
+impl ResourcePolicy {
+    // empty timezone means UTC
+    pub fn tz(&self) -> Result<Tz, Error> {
+        if self.timezone.is_empty() {
+            return Ok(Tz::UTC);
+        }
+        self.timezone
+            .parse()
+            .map_err(|_| Error::InvalidPolicy(format!("unknown timezone: {}", self.timezone)))
+    }
+
+    fn has_closed_hours(&self) -> bool {
+        !self.opening_hours.is_empty() || !self.holiday_calendars.is_empty()
+    }
+
+    // the open parts of [start, end), closed hours and holidays are cut out. adjacent parts are merged,
+    // so a reservation is allowed only if the result is exactly [start, end)
+    pub fn open_intervals(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        holidays: &[NaiveDate],
+    ) -> Result<Vec<Interval>, Error> {
+        if start >= end {
+            return Ok(vec![]);
+        }
+        if !self.has_closed_hours() {
+            return Ok(vec![(start, end)]);
+        }
+
+        let tz = self.tz()?;
+        let last_day = end.with_timezone(&tz).date_naive();
+        let mut day = Some(start.with_timezone(&tz).date_naive());
+        let mut intervals: Vec<Interval> = vec![];
+
+        while let Some(date) = day.filter(|d| *d <= last_day) {
+            day = date.succ_opt();
+            if holidays.contains(&date) {
+                continue;
+            }
+
+            let weekday = date.weekday().number_from_monday() as i32;
+            let hours: Vec<(i32, i32)> = if self.opening_hours.is_empty() {
+                vec![(0, MINUTES_PER_DAY)]
+            } else {
+                self.opening_hours
+                    .iter()
+                    .filter(|h| h.weekday == weekday)
+                    .map(|h| (h.open_minute, h.close_minute))
+                    .collect()
+            };
+
+            for (open, close) in hours {
+                let open = local_to_utc(&tz, date, open).max(start);
+                let close = local_to_utc(&tz, date, close).min(end);
+                if open < close {
+                    intervals.push((open, close));
+                }
+            }
+        }
+
+        intervals.sort();
+        let mut merged: Vec<Interval> = vec![];
+        for (open, close) in intervals {
+            match merged.last_mut() {
+                Some(last) if open <= last.1 => last.1 = last.1.max(close),
+                _ => merged.push((open, close)),
+            }
+        }
+        Ok(merged)
+    }
+
+    // the whole reservation must be in the opening hours and not on a holiday
+    pub fn check_opening_hours(
+        &self,
+        rsvp: &Reservation,
+        holidays: &[NaiveDate],
+    ) -> Result<(), Error> {
+        if !self.has_closed_hours() {
+            return Ok(());
+        }
+
+        let start = convert_time_to_utc(rsvp.start.as_ref().ok_or(Error::InvalidTime)?)?;
+        // open-ended reservation would run into the closed hours sooner or later
+        let Some(end) = rsvp.end.as_ref() else {
+            return Err(Error::OutsideOpeningHours);
+        };
+        let end = convert_time_to_utc(end)?;
+
+        // walk one local day at a time and stop at the first gap, so a long reservation is rejected early
+        let tz = self.tz()?;
+        let mut date = start.with_timezone(&tz).date_naive();
+        let mut covered = start;
+        let mut full_days = 0;
+        while covered < end {
+            // a whole week open all day means every weekday is, only holidays can close the rest
+            if full_days >= 7 {
+                if holidays
+                    .iter()
+                    .any(|h| *h >= date && local_to_utc(&tz, *h, 0) < end)
+                {
+                    return Err(Error::OutsideOpeningHours);
+                }
+                return Ok(());
+            }
+
+            let day_start = local_to_utc(&tz, date, 0);
+            let next = date.succ_opt().ok_or(Error::InvalidTime)?;
+            let day_end = local_to_utc(&tz, next, 0).min(end);
+            if self.open_intervals(covered, day_end, holidays)? != [(covered, day_end)] {
+                return Err(Error::OutsideOpeningHours);
+            }
+            full_days = if covered == day_start {
+                full_days + 1
+            } else {
+                0
+            };
+            covered = day_end;
+            date = next;
+        }
+        Ok(())
+    }
+
+    // [monday 00:00, next monday 00:00) in local time of each week [start, end) overlaps, in order.
+    // open-ended is only in the week it starts
+    pub fn weeks_of(
+        &self,
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Interval>, Error> {
+        let tz = self.tz()?;
+        let date = start.with_timezone(&tz).date_naive();
+        let mut monday = date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+        let mut week_start = local_to_utc(&tz, monday, 0);
+        let mut weeks = vec![];
+        loop {
+            monday += Duration::days(7);
+            let week_end = local_to_utc(&tz, monday, 0);
+            weeks.push((week_start, week_end));
+            match end {
+                Some(end) if week_end < end => week_start = week_end,
+                _ => return Ok(weeks),
+            }
+        }
+    }
+}
+
+// local time which doesn't exist (skipped by DST) is moved one hour later
+fn local_to_utc(tz: &Tz, date: NaiveDate, minute: i32) -> DateTime<Utc> {
+    let naive = date.and_time(Default::default()) + Duration::minutes(i64::from(minute));
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+impl Validator for OpeningHours {
+    fn validate(&self) -> Result<(), Error> {
+        if !(1..=7).contains(&self.weekday) {
+            return Err(Error::InvalidPolicy(format!(
+                "weekday must be 1 to 7, got {}",
+                self.weekday
+            )));
+        }
+        if self.open_minute < 0
+            || self.open_minute >= self.close_minute
+            || self.close_minute > MINUTES_PER_DAY
+        {
+            return Err(Error::InvalidPolicy(format!(
+                "invalid opening hours {} to {}",
+                self.open_minute, self.close_minute
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Holiday {
+    pub fn day(&self) -> Result<NaiveDate, Error> {
+        NaiveDate::parse_from_str(&self.date, HOLIDAY_DATE_FORMAT)
+            .map_err(|_| Error::InvalidPolicy(format!("invalid holiday date: {}", self.date)))
+    }
+}
+
+impl Validator for HolidayCalendar {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::InvalidPolicy(
+                "holiday calendar id is required".to_string(),
+            ));
+        }
+        for holiday in &self.holidays {
+            holiday.day()?;
+        }
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for OpeningHours {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            weekday: row.try_get("weekday")?,
+            open_minute: row.try_get("open_minute")?,
+            close_minute: row.try_get("close_minute")?,
+        })
+    }
+}
+
+impl FromRow<'_, PgRow> for Holiday {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            date: row
+                .try_get::<NaiveDate, _>("day")?
+                .format(HOLIDAY_DATE_FORMAT)
+                .to_string(),
+            name: row.try_get("name")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // weekdays 08:00 - 20:00 in Taipei
+    fn office_policy() -> ResourcePolicy {
+        ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            timezone: "Asia/Taipei".to_string(),
+            opening_hours: (1..=5)
+                .map(|weekday| OpeningHours {
+                    weekday,
+                    open_minute: 8 * 60,
+                    close_minute: 20 * 60,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn rsvp(start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(
+            "yangid",
+            "room-1",
+            start.parse().unwrap(),
+            end.parse().unwrap(),
+            "",
+        )
+    }
+
+    #[test]
+    fn reservation_in_opening_hours_should_pass() {
+        let policy = office_policy();
+        // 2023-01-02 is Monday
+        let r = rsvp("2023-01-02T08:00:00+0800", "2023-01-02T20:00:00+0800");
+        assert!(policy.check_opening_hours(&r, &[]).is_ok());
+
+        // same time in another timezone
+        let r = rsvp("2023-01-02T00:00:00+0000", "2023-01-02T01:00:00+0000");
+        assert!(policy.check_opening_hours(&r, &[]).is_ok());
+    }
+
+    #[test]
+    fn reservation_outside_opening_hours_should_reject() {
+        let policy = office_policy();
+        let cases = [
+            // too early
+            ("2023-01-02T07:30:00+0800", "2023-01-02T09:00:00+0800"),
+            // over the night
+            ("2023-01-02T19:00:00+0800", "2023-01-03T09:00:00+0800"),
+            // saturday
+            ("2023-01-07T10:00:00+0800", "2023-01-07T11:00:00+0800"),
+        ];
+        for (start, end) in cases {
+            let r = rsvp(start, end);
+            assert_eq!(
+                policy.check_opening_hours(&r, &[]),
+                Err(Error::OutsideOpeningHours)
+            );
+        }
+
+        let r = Reservation::new_open_ended(
+            "yangid",
+            "room-1",
+            "2023-01-02T08:00:00+0800".parse().unwrap(),
+            "",
+        );
+        assert_eq!(
+            policy.check_opening_hours(&r, &[]),
+            Err(Error::OutsideOpeningHours)
+        );
+    }
+
+    #[test]
+    fn reservation_on_holiday_should_reject() {
+        let policy = office_policy();
+        let holiday = NaiveDate::from_ymd_opt(2023, 1, 2).unwrap();
+        let r = rsvp("2023-01-02T10:00:00+0800", "2023-01-02T11:00:00+0800");
+        assert_eq!(
+            policy.check_opening_hours(&r, &[holiday]),
+            Err(Error::OutsideOpeningHours)
+        );
+
+        // holidays only, open all day otherwise
+        let policy = ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            holiday_calendars: vec!["tw".to_string()],
+            ..Default::default()
+        };
+        let r = rsvp("2023-01-03T00:00:00+0000", "2023-01-05T00:00:00+0000");
+        assert!(policy.check_opening_hours(&r, &[holiday]).is_ok());
+        let r = rsvp("2023-01-01T12:00:00+0000", "2023-01-03T00:00:00+0000");
+        assert_eq!(
+            policy.check_opening_hours(&r, &[holiday]),
+            Err(Error::OutsideOpeningHours)
+        );
+    }
+
+    #[test]
+    fn long_reservation_should_be_checked_without_walking_every_day() {
+        let r = rsvp("0001-01-01T00:00:00Z", "9999-01-01T00:00:00Z");
+        assert_eq!(
+            office_policy().check_opening_hours(&r, &[]),
+            Err(Error::OutsideOpeningHours)
+        );
+
+        // open all day every day, only the holidays matter
+        let policy = ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            timezone: "Asia/Taipei".to_string(),
+            opening_hours: (1..=7)
+                .map(|weekday| OpeningHours {
+                    weekday,
+                    open_minute: 0,
+                    close_minute: MINUTES_PER_DAY,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        assert!(policy.check_opening_hours(&r, &[]).is_ok());
+        let holiday = NaiveDate::from_ymd_opt(5000, 1, 1).unwrap();
+        assert_eq!(
+            policy.check_opening_hours(&r, &[holiday]),
+            Err(Error::OutsideOpeningHours)
+        );
+        let after = NaiveDate::from_ymd_opt(9999, 1, 2).unwrap();
+        assert!(policy.check_opening_hours(&r, &[after]).is_ok());
+    }
+
+    #[test]
+    fn open_intervals_should_skip_closed_hours() {
+        let policy = office_policy();
+        let start = "2023-01-06T00:00:00+0800".parse().unwrap();
+        let end = "2023-01-10T00:00:00+0800".parse().unwrap();
+        let holiday = NaiveDate::from_ymd_opt(2023, 1, 9).unwrap();
+
+        let intervals = policy.open_intervals(start, end, &[holiday]).unwrap();
+        // friday only, weekend and monday holiday are closed
+        let expected: Interval = (
+            "2023-01-06T08:00:00+0800".parse().unwrap(),
+            "2023-01-06T20:00:00+0800".parse().unwrap(),
+        );
+        assert_eq!(intervals, vec![expected]);
+    }
+
+    #[test]
+    fn weeks_should_be_local_and_cover_the_reservation() {
+        let policy = office_policy();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        // sunday 17:00 UTC is monday 01:00 in Taipei, the week starts at monday 00:00 local
+        let weeks = policy
+            .weeks_of(at("2030-01-13T17:00:00Z"), Some(at("2030-01-13T18:00:00Z")))
+            .unwrap();
+        assert_eq!(
+            weeks,
+            vec![(at("2030-01-13T16:00:00Z"), at("2030-01-20T16:00:00Z"))]
+        );
+        // across the week boundary, both weeks
+        let weeks = policy
+            .weeks_of(at("2030-01-13T15:00:00Z"), Some(at("2030-01-13T17:00:00Z")))
+            .unwrap();
+        assert_eq!(
+            weeks,
+            vec![
+                (at("2030-01-06T16:00:00Z"), at("2030-01-13T16:00:00Z")),
+                (at("2030-01-13T16:00:00Z"), at("2030-01-20T16:00:00Z")),
+            ]
+        );
+        // ending at the boundary, open-ended
+        assert_eq!(
+            policy
+                .weeks_of(at("2030-01-13T15:00:00Z"), Some(at("2030-01-13T16:00:00Z")))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            policy
+                .weeks_of(at("2030-01-13T15:00:00Z"), None)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn opening_hours_should_be_validated() {
+        let mut policy = office_policy();
+        assert!(policy.tz().is_ok());
+        policy.timezone = "Mars/Olympus".to_string();
+        assert!(policy.tz().is_err());
+
+        let hours = |weekday, open_minute, close_minute| OpeningHours {
+            weekday,
+            open_minute,
+            close_minute,
+        };
+        assert!(hours(1, 0, 1440).validate().is_ok());
+        assert!(hours(0, 0, 60).validate().is_err());
+        assert!(hours(1, 60, 60).validate().is_err());
+        assert!(hours(1, 0, 1441).validate().is_err());
+
+        let mut calendar = HolidayCalendar {
+            id: "tw".to_string(),
+            holidays: vec![Holiday {
+                date: "2023-01-02".to_string(),
+                name: "New Year".to_string(),
+            }],
+        };
+        assert!(calendar.validate().is_ok());
+        calendar.holidays[0].date = "2023/01/02".to_string();
+        assert!(calendar.validate().is_err());
+    }
+}
@@ -16,23 +16,33 @@ macro_rules! impl_new {
             }
         }
     };
-    // 用於匹配多個參數並且有id的結構體
+    // 用於匹配多個參數並且有id的結構體，其他欄位 (例如 idempotency_key) 用預設值
     ($($name:ident),* $(,)?) => {
         $(
             impl $name {
+                #[allow(clippy::needless_update)]
                 pub fn new(id: i64) -> Self {
-                    Self { id }
+                    Self { id, ..Default::default() }
                 }
             }
         )*
     };
 }
 
-impl_new!(single ReserveRequest, reservation, Reservation);
 impl_new!(ConfirmRequest, GetRequest, CancelRequest);
 impl_new!(single FilterRequest, query, FilterById);
 impl_new!(single QueryRequest, query, ReservationQuery);
 
+// ReserveRequest has optional flags besides the reservation, so it's not in the macro
+impl ReserveRequest {
+    pub fn new(rsvp: Reservation) -> Self {
+        Self {
+            reservation: Some(rsvp),
+            ..Default::default()
+        }
+    }
+}
+
 // TODO: 這邊的macro有點複雜，需要再研究一下
 
 // impl ReserveRequest {
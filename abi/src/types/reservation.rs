@@ -1,4 +1,5 @@
 use chrono::{DateTime, FixedOffset, Utc};
+use prost_types::Timestamp;
 use sqlx::{
     postgres::{types::PgRange, PgRow},
     FromRow, Row,
@@ -6,8 +7,10 @@ use sqlx::{
 use std::ops::Bound;
 
 use crate::{
-    convert_timestamp_into_timespan_pgrange, types::reservation_status::RsvpStatus,
-    utils::convert_to_timestamp, validate_range, Error, Reservation, ReservationStatus, Validator,
+    convert_time_to_utc, convert_timestamp_into_timespan_pgrange,
+    types::reservation_status::RsvpStatus, utils::convert_to_timestamp, validate_range, Error,
+    ErrorCode, Reservation, ReservationStatus, ReservationWindow, StatusDetail, Validator,
+    ERROR_CODE_METADATA_KEY,
 };
 
 impl Reservation {
@@ -18,53 +21,110 @@ impl Reservation {
         end: DateTime<FixedOffset>,
         note: impl Into<String>,
     ) -> Self {
+        let uid = uid.into();
         Self {
             id: 0,
-            user_id: uid.into(),
+            // booked by the owner
+            created_by: uid.clone(),
+            user_id: uid,
             status: ReservationStatus::Pending as i32,
             resource_id: rid.into(),
             start: Some(convert_to_timestamp(start.with_timezone(&Utc))),
             end: Some(convert_to_timestamp(end.with_timezone(&Utc))),
             note: note.into(),
+            ..Default::default()
         }
     }
 
-    pub fn get_timestamp(&self) -> PgRange<DateTime<Utc>> {
-        convert_timestamp_into_timespan_pgrange(
-            Some(self.start.as_ref().unwrap()),
-            Some(self.end.as_ref().unwrap()),
-        )
+    // indefinite hold, e.g. "assigned desk from Monday onward"
+    pub fn new_open_ended(
+        uid: impl Into<String>,
+        rid: impl Into<String>,
+        start: DateTime<FixedOffset>,
+        note: impl Into<String>,
+    ) -> Self {
+        let uid = uid.into();
+        Self {
+            id: 0,
+            // booked by the owner
+            created_by: uid.clone(),
+            user_id: uid,
+            status: ReservationStatus::Pending as i32,
+            resource_id: rid.into(),
+            start: Some(convert_to_timestamp(start.with_timezone(&Utc))),
+            end: None,
+            note: note.into(),
+            ..Default::default()
+        }
+    }
+
+    // who made it, an empty created_by is the user booking for themselves
+    pub fn booked_by(&self) -> &str {
+        if self.created_by.is_empty() {
+            &self.user_id
+        } else {
+            &self.created_by
+        }
+    }
+
+    pub fn get_timestamp(&self) -> Result<PgRange<DateTime<Utc>>, Error> {
+        convert_timestamp_into_timespan_pgrange(self.start.as_ref(), self.end.as_ref())
+    }
+
+    // for the client, the current reservation sent back with VERSION_MISMATCH
+    pub fn from_version_mismatch(status: &tonic::Status) -> Option<Self> {
+        let code = status.metadata().get(ERROR_CODE_METADATA_KEY)?;
+        if code != ErrorCode::VersionMismatch.as_str() {
+            return None;
+        }
+        Self::from_status_details(status)
+    }
+
+    // used to build ReservationConflict without parsing the database error message
+    pub fn window(&self) -> Result<ReservationWindow, Error> {
+        let convert = |ts: Option<&Timestamp>| ts.map(convert_time_to_utc).transpose();
+        Ok(ReservationWindow {
+            rid: self.resource_id.clone(),
+            start: convert(self.start.as_ref())?,
+            end: convert(self.end.as_ref())?,
+        })
     }
 }
 
 // to make sure change_status() works, we need to implement FromRow trait for Reservation
 impl FromRow<'_, PgRow> for Reservation {
     fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
-        let range: PgRange<DateTime<Utc>> = row.get("timespan");
+        let range: PgRange<DateTime<Utc>> = row.try_get("timespan")?;
         let range: NativeRange<DateTime<Utc>> = range.into();
 
-        let start = range.start.unwrap();
-        let end = range.end.unwrap();
-
+        // use try_get and return decode error instead of panic on unexpected data
         Ok(Self {
-            id: row.get::<i64, _>("id"),
-            user_id: row.get("user_id"),
-            resource_id: row.get("resource_id"),
-            start: Some(convert_to_timestamp(start)),
-            end: Some(convert_to_timestamp(end)),
-            note: row.get("note"),
-            status: ReservationStatus::from(row.get::<RsvpStatus, _>("status")) as i32,
+            id: row.try_get::<i64, _>("id")?,
+            user_id: row.try_get("user_id")?,
+            resource_id: row.try_get("resource_id")?,
+            // unbounded side is None, e.g. open-ended reservation has no end
+            start: range.start.map(convert_to_timestamp),
+            end: range.end.map(convert_to_timestamp),
+            note: row
+                .try_get::<Option<String>, _>("note")?
+                .unwrap_or_default(),
+            status: ReservationStatus::from(row.try_get::<RsvpStatus, _>("status")?) as i32,
+            version: row.try_get("version")?,
+            created_at: Some(convert_to_timestamp(row.try_get("created_at")?)),
+            updated_at: Some(convert_to_timestamp(row.try_get("updated_at")?)),
+            created_by: row.try_get("created_by")?,
         })
     }
 }
 
 // to make sure we can divided the "timespan" to get start and end side by side.
-struct NativeRange<T> {
-    start: Option<T>,
-    end: Option<T>,
+pub(crate) struct NativeRange<T> {
+    pub start: Option<T>,
+    pub end: Option<T>,
 }
 
 // if we want to unwrap the PgRange, we have to match the Bound enum to make sure that every branch is considered.
+// reservations are always stored as [start, end), so included/excluded makes no difference here.
 
 impl<T> From<PgRange<T>> for NativeRange<T> {
     fn from(range: PgRange<T>) -> Self {
@@ -90,10 +150,14 @@ impl Validator for Reservation {
             return Err(Error::InvalidResourceId(self.resource_id.clone()));
         }
 
-        validate_range(
-            Some(self.start.as_ref().unwrap()),
-            Some(self.end.as_ref().unwrap()),
-        )?;
+        match (self.start.as_ref(), self.end.as_ref()) {
+            (Some(_), Some(_)) => validate_range(self.start.as_ref(), self.end.as_ref())?,
+            // open-ended reservation, only start is required
+            (Some(start), None) => {
+                convert_time_to_utc(start)?;
+            }
+            (None, _) => return Err(Error::InvalidTime),
+        }
         Ok(())
     }
 }
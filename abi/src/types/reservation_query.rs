@@ -2,25 +2,20 @@ use chrono::{DateTime, Utc};
 use sqlx::postgres::types::PgRange;
 
 use crate::{
-    convert_timestamp_into_timespan_pgrange, validate_range, Error, ReservationQuery, Validator,
+    convert_timestamp_into_window_pgrange, validate_window, Error, ReservationQuery, Validator,
 };
 
 impl ReservationQuery {
-    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
-        convert_timestamp_into_timespan_pgrange(
-            Some(self.start.as_ref().unwrap()),
-            Some(self.end.as_ref().unwrap()),
-        )
+    pub fn get_timespan(&self) -> Result<PgRange<DateTime<Utc>>, Error> {
+        convert_timestamp_into_window_pgrange(self.start.as_ref(), self.end.as_ref())
     }
 }
 
 impl Validator for ReservationQuery {
     fn validate(&self) -> Result<(), Error> {
-        validate_range(
-            Some(self.start.as_ref().unwrap()),
-            Some(self.end.as_ref().unwrap()),
-        )?;
-
-        Ok(())
+        // start and end are optional for query, missing one means unbounded
+        validate_window(self.start.as_ref(), self.end.as_ref())?;
+        self.created_between.validate()?;
+        self.updated_between.validate()
     }
 }
@@ -0,0 +1,65 @@
+use sqlx::{postgres::PgRow, FromRow, Row};
+
+use crate::{Error, Resource, Validator};
+
+impl Resource {
+    // resource never set is a root of its own
+    pub fn root(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Validator for Resource {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::InvalidResourceId(self.id.clone()));
+        }
+        // deeper cycles are checked against the tree when saved
+        if self.parent_id == self.id {
+            return Err(Error::InvalidResourceId(self.parent_id.clone()));
+        }
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for Resource {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let parent_id: Option<String> = row.try_get("parent_id")?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            parent_id: parent_id.unwrap_or_default(),
+            // attributes are in another table
+            attributes: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_should_be_validated() {
+        let resource = Resource {
+            id: "room-301".to_string(),
+            parent_id: "floor-3".to_string(),
+            ..Default::default()
+        };
+        assert!(resource.validate().is_ok());
+        assert!(Resource::root("campus").validate().is_ok());
+
+        assert!(Resource::root("").validate().is_err());
+        let resource = Resource {
+            id: "floor-3".to_string(),
+            parent_id: "floor-3".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            resource.validate(),
+            Err(Error::InvalidResourceId("floor-3".to_string()))
+        );
+    }
+}
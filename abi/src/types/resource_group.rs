@@ -0,0 +1,45 @@
+use crate::{Error, ResourceGroup, Validator};
+
+impl Validator for ResourceGroup {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::InvalidResourceId(self.id.clone()));
+        }
+        // a group can't contain itself, cycles through nested groups are checked when saved
+        for (i, member) in self.member_ids.iter().enumerate() {
+            if member.is_empty() || *member == self.id || self.member_ids[..i].contains(member) {
+                return Err(Error::InvalidResourceId(member.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(id: &str, members: &[&str]) -> ResourceGroup {
+        ResourceGroup {
+            id: id.to_string(),
+            member_ids: members.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn resource_group_should_be_validated() {
+        assert!(group("room-ab", &["room-a", "room-b"]).validate().is_ok());
+        assert!(group("room-ab", &[]).validate().is_ok());
+
+        assert!(group("", &["room-a"]).validate().is_err());
+        assert_eq!(
+            group("room-ab", &["room-a", "room-ab"]).validate(),
+            Err(Error::InvalidResourceId("room-ab".to_string()))
+        );
+        assert_eq!(
+            group("room-ab", &["room-a", "room-a"]).validate(),
+            Err(Error::InvalidResourceId("room-a".to_string()))
+        );
+        assert!(group("room-ab", &["room-a", ""]).validate().is_err());
+    }
+}
@@ -0,0 +1,458 @@
+use chrono::{DateTime, Duration, Offset, Utc};
+use prost_types::Timestamp;
+use sqlx::{postgres::PgRow, FromRow, Row};
+use std::fmt;
+
+use super::MINUTES_PER_DAY;
+use crate::{
+    convert_time_to_utc, convert_to_timestamp, Error, Reservation, ResourcePolicy, SlotAdjustment,
+    Validator,
+};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+impl ResourcePolicy {
+    // resource without policy row has no restriction
+    pub fn unrestricted(rid: impl Into<String>) -> Self {
+        Self {
+            resource_id: rid.into(),
+            ..Default::default()
+        }
+    }
+
+    // check start/end against the slot, snap them if the policy allows, otherwise reject.
+    // the reservation should be validated before, so start is present and convertible.
+    pub fn apply_slot(&self, rsvp: &mut Reservation) -> Result<(), Error> {
+        if self.slot_minutes <= 0 {
+            return Ok(());
+        }
+        let slot = i128::from(self.slot_minutes) * 60 * NANOS_PER_SECOND;
+
+        let start_ts = rsvp.start.as_ref().ok_or(Error::InvalidTime)?;
+        let start = to_nanos(start_ts);
+        let snapped_start = if self.align_to_slot {
+            let offset = self.utc_offset_nanos(convert_time_to_utc(start_ts)?)?;
+            start - (start + offset).rem_euclid(slot)
+        } else {
+            start
+        };
+        // open-ended reservation has no end to snap
+        let end = rsvp.end.as_ref().map(to_nanos);
+        let snapped_end = end.map(|end| {
+            let slots = (end - snapped_start + slot - 1).div_euclid(slot);
+            snapped_start + slots * slot
+        });
+
+        if snapped_start == start && snapped_end == end {
+            return Ok(());
+        }
+
+        match SlotAdjustment::from_i32(self.adjustment) {
+            Some(SlotAdjustment::Snap) => {
+                rsvp.start = Some(from_nanos(snapped_start)?);
+                rsvp.end = snapped_end.map(from_nanos).transpose()?;
+                Ok(())
+            }
+            _ => Err(Error::SlotMisaligned(self.slot_minutes)),
+        }
+    }
+
+    // the first slot boundary at or after t, t itself if the slot is not aligned
+    pub fn ceil_to_slot(&self, t: DateTime<Utc>) -> Result<DateTime<Utc>, Error> {
+        if !self.align_to_slot || self.slot_minutes <= 0 {
+            return Ok(t);
+        }
+        let slot = i128::from(self.slot_minutes) * 60 * NANOS_PER_SECOND;
+        let nanos = to_nanos(&convert_to_timestamp(t));
+        let local = nanos + self.utc_offset_nanos(t)?;
+        let ceiled = nanos + (slot - local.rem_euclid(slot)).rem_euclid(slot);
+        convert_time_to_utc(&from_nanos(ceiled)?)
+    }
+
+    // slots are counted from local midnight, so e.g. Asia/Kolkata (+05:30) still gets 10:00, 11:00 for 60 minutes slot
+    fn utc_offset_nanos(&self, t: DateTime<Utc>) -> Result<i128, Error> {
+        let offset = t
+            .with_timezone(&self.tz()?)
+            .offset()
+            .fix()
+            .local_minus_utc();
+        Ok(i128::from(offset) * NANOS_PER_SECOND)
+    }
+
+    // check duration and how far ahead the reservation starts, now is given so it can be tested
+    pub fn check_duration(&self, rsvp: &Reservation, now: DateTime<Utc>) -> Result<(), Error> {
+        let start = convert_time_to_utc(rsvp.start.as_ref().ok_or(Error::InvalidTime)?)?;
+        let end = rsvp.end.as_ref().map(convert_time_to_utc).transpose()?;
+
+        // open-ended reservation is longer than any maximum
+        let duration = end.map(|end| end - start);
+        if self.min_duration_minutes > 0
+            && duration.is_some_and(|d| d < minutes(self.min_duration_minutes))
+        {
+            return Err(Error::DurationTooShort(self.min_duration_minutes));
+        }
+        if self.max_duration_minutes > 0
+            && duration.is_none_or(|d| d > minutes(self.max_duration_minutes))
+        {
+            return Err(Error::DurationTooLong(self.max_duration_minutes));
+        }
+
+        let ahead = start - now;
+        if self.min_advance_minutes > 0 && ahead < minutes(self.min_advance_minutes) {
+            return Err(Error::StartTooSoon(self.min_advance_minutes));
+        }
+        if self.max_advance_minutes > 0 && ahead > minutes(self.max_advance_minutes) {
+            return Err(Error::StartTooFarAhead(self.max_advance_minutes));
+        }
+        Ok(())
+    }
+}
+
+fn minutes(v: i32) -> Duration {
+    Duration::minutes(i64::from(v))
+}
+
+fn to_nanos(ts: &Timestamp) -> i128 {
+    i128::from(ts.seconds) * NANOS_PER_SECOND + i128::from(ts.nanos)
+}
+
+fn from_nanos(nanos: i128) -> Result<Timestamp, Error> {
+    Ok(Timestamp {
+        seconds: i64::try_from(nanos.div_euclid(NANOS_PER_SECOND))
+            .map_err(|_| Error::InvalidTime)?,
+        nanos: nanos.rem_euclid(NANOS_PER_SECOND) as i32,
+    })
+}
+
+impl Validator for ResourcePolicy {
+    fn validate(&self) -> Result<(), Error> {
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+        if self.slot_minutes < 0 {
+            return Err(Error::InvalidPolicy(
+                "slot_minutes must not be negative".to_string(),
+            ));
+        }
+        // otherwise the boundary would drift from day to day
+        if self.align_to_slot && self.slot_minutes > 0 && MINUTES_PER_DAY % self.slot_minutes != 0 {
+            return Err(Error::InvalidPolicy(
+                "aligned slot_minutes must divide a day".to_string(),
+            ));
+        }
+        let limits = [
+            self.min_duration_minutes,
+            self.max_duration_minutes,
+            self.min_advance_minutes,
+            self.max_advance_minutes,
+        ];
+        if limits.iter().any(|v| *v < 0) {
+            return Err(Error::InvalidPolicy(
+                "duration and advance minutes must not be negative".to_string(),
+            ));
+        }
+        if self.max_duration_minutes > 0 && self.min_duration_minutes > self.max_duration_minutes {
+            return Err(Error::InvalidPolicy(
+                "min_duration_minutes is larger than max_duration_minutes".to_string(),
+            ));
+        }
+        if self.max_advance_minutes > 0 && self.min_advance_minutes > self.max_advance_minutes {
+            return Err(Error::InvalidPolicy(
+                "min_advance_minutes is larger than max_advance_minutes".to_string(),
+            ));
+        }
+        if SlotAdjustment::from_i32(self.adjustment).is_none() {
+            return Err(Error::InvalidPolicy(format!(
+                "unknown adjustment: {}",
+                self.adjustment
+            )));
+        }
+        self.tz()?;
+        for hours in &self.opening_hours {
+            hours.validate()?;
+        }
+        if self.holiday_calendars.iter().any(|id| id.is_empty()) {
+            return Err(Error::InvalidPolicy(
+                "holiday calendar id is required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SlotAdjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "unknown"),
+            Self::Reject => write!(f, "reject"),
+            Self::Snap => write!(f, "snap"),
+        }
+    }
+}
+
+// database equivalent of the "slot_adjustment" enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "slot_adjustment", rename_all = "lowercase")]
+enum RsvpSlotAdjustment {
+    Unknown,
+    Reject,
+    Snap,
+}
+
+impl From<RsvpSlotAdjustment> for SlotAdjustment {
+    fn from(adjustment: RsvpSlotAdjustment) -> Self {
+        match adjustment {
+            RsvpSlotAdjustment::Unknown => Self::Unknown,
+            RsvpSlotAdjustment::Reject => Self::Reject,
+            RsvpSlotAdjustment::Snap => Self::Snap,
+        }
+    }
+}
+
+impl FromRow<'_, PgRow> for ResourcePolicy {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            resource_id: row.try_get("resource_id")?,
+            slot_minutes: row.try_get("slot_minutes")?,
+            align_to_slot: row.try_get("align_to_slot")?,
+            adjustment: SlotAdjustment::from(row.try_get::<RsvpSlotAdjustment, _>("adjustment")?)
+                as i32,
+            min_duration_minutes: row.try_get("min_duration_minutes")?,
+            max_duration_minutes: row.try_get("max_duration_minutes")?,
+            min_advance_minutes: row.try_get("min_advance_minutes")?,
+            max_advance_minutes: row.try_get("max_advance_minutes")?,
+            timezone: row.try_get("timezone")?,
+            // opening hours are stored in another table
+            opening_hours: vec![],
+            holiday_calendars: row.try_get("holiday_calendars")?,
+            resource_type: row.try_get("resource_type")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_policy(
+        slot_minutes: i32,
+        align_to_slot: bool,
+        adjustment: SlotAdjustment,
+    ) -> ResourcePolicy {
+        ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            slot_minutes,
+            align_to_slot,
+            adjustment: adjustment as i32,
+            ..Default::default()
+        }
+    }
+
+    fn rsvp(start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(
+            "yangid",
+            "room-1",
+            start.parse().unwrap(),
+            end.parse().unwrap(),
+            "",
+        )
+    }
+
+    #[test]
+    fn aligned_reservation_should_pass() {
+        let policy = make_policy(15, true, SlotAdjustment::Reject);
+        let mut r = rsvp("2023-01-01T10:15:00+0800", "2023-01-01T11:00:00+0800");
+        let expected = r.clone();
+        policy.apply_slot(&mut r).unwrap();
+        assert_eq!(r, expected);
+
+        // no restriction
+        let mut r = rsvp("2023-01-01T10:03:17.123+0800", "2023-01-01T10:58:00+0800");
+        ResourcePolicy::unrestricted("room-1")
+            .apply_slot(&mut r)
+            .unwrap();
+    }
+
+    #[test]
+    fn ceil_to_slot_should_move_to_the_next_boundary() {
+        let t = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let policy = make_policy(15, true, SlotAdjustment::Reject);
+        assert_eq!(
+            policy.ceil_to_slot(t("2023-01-01T10:03:17.123Z")),
+            Ok(t("2023-01-01T10:15:00Z"))
+        );
+        assert_eq!(
+            policy.ceil_to_slot(t("2023-01-01T10:15:00Z")),
+            Ok(t("2023-01-01T10:15:00Z"))
+        );
+        // not aligned, nothing to do
+        let policy = make_policy(15, false, SlotAdjustment::Reject);
+        assert_eq!(
+            policy.ceil_to_slot(t("2023-01-01T10:03:00Z")),
+            Ok(t("2023-01-01T10:03:00Z"))
+        );
+    }
+
+    #[test]
+    fn slot_should_be_aligned_in_policy_timezone() {
+        let t = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let mut policy = make_policy(60, true, SlotAdjustment::Snap);
+        policy.timezone = "Asia/Kolkata".to_string();
+
+        // 10:00 in Kolkata is 04:30 UTC
+        let mut r = rsvp("2023-01-01T10:00:00+0530", "2023-01-01T11:00:00+0530");
+        let expected = r.clone();
+        policy.apply_slot(&mut r).unwrap();
+        assert_eq!(r, expected);
+
+        let mut r = rsvp("2023-01-01T10:20:00+0530", "2023-01-01T10:50:00+0530");
+        policy.apply_slot(&mut r).unwrap();
+        assert_eq!(
+            r,
+            rsvp("2023-01-01T10:00:00+0530", "2023-01-01T11:00:00+0530")
+        );
+
+        assert_eq!(
+            policy.ceil_to_slot(t("2023-01-01T05:00:00Z")),
+            Ok(t("2023-01-01T05:30:00Z"))
+        );
+
+        // +05:45, a whole-hour boundary in UTC is not one locally
+        policy.timezone = "Asia/Kathmandu".to_string();
+        policy.adjustment = SlotAdjustment::Reject as i32;
+        let mut r = rsvp("2023-01-01T04:00:00+0000", "2023-01-01T05:00:00+0000");
+        assert_eq!(policy.apply_slot(&mut r), Err(Error::SlotMisaligned(60)));
+        let mut r = rsvp("2023-01-01T10:00:00+0545", "2023-01-01T11:00:00+0545");
+        assert!(policy.apply_slot(&mut r).is_ok());
+    }
+
+    #[test]
+    fn misaligned_reservation_should_be_rejected() {
+        let policy = make_policy(15, true, SlotAdjustment::Reject);
+        let mut r = rsvp("2023-01-01T10:03:17.123+0800", "2023-01-01T10:58:00+0800");
+        assert_eq!(policy.apply_slot(&mut r), Err(Error::SlotMisaligned(15)));
+
+        // unknown adjustment is the same as reject
+        let policy = make_policy(15, false, SlotAdjustment::Unknown);
+        let mut r = rsvp("2023-01-01T10:03:00+0800", "2023-01-01T10:10:00+0800");
+        assert_eq!(policy.apply_slot(&mut r), Err(Error::SlotMisaligned(15)));
+    }
+
+    #[test]
+    fn misaligned_reservation_should_be_snapped() {
+        let policy = make_policy(15, true, SlotAdjustment::Snap);
+        let mut r = rsvp("2023-01-01T10:03:17.123+0800", "2023-01-01T10:58:00+0800");
+        policy.apply_slot(&mut r).unwrap();
+        assert_eq!(
+            r,
+            rsvp("2023-01-01T10:00:00+0800", "2023-01-01T11:00:00+0800")
+        );
+
+        // without alignment only the duration is rounded up
+        let policy = make_policy(15, false, SlotAdjustment::Snap);
+        let mut r = rsvp("2023-01-01T10:03:00+0800", "2023-01-01T10:10:00+0800");
+        policy.apply_slot(&mut r).unwrap();
+        assert_eq!(
+            r,
+            rsvp("2023-01-01T10:03:00+0800", "2023-01-01T10:18:00+0800")
+        );
+
+        // open-ended reservation only snaps start
+        let policy = make_policy(15, true, SlotAdjustment::Snap);
+        let mut r = Reservation::new_open_ended(
+            "yangid",
+            "room-1",
+            "2023-01-01T10:03:00+0800".parse().unwrap(),
+            "",
+        );
+        policy.apply_slot(&mut r).unwrap();
+        assert_eq!(r.start.unwrap().seconds % (15 * 60), 0);
+        assert_eq!(r.end, None);
+    }
+
+    #[test]
+    fn policy_should_be_validated() {
+        assert!(make_policy(15, true, SlotAdjustment::Snap)
+            .validate()
+            .is_ok());
+        assert!(make_policy(-1, false, SlotAdjustment::Reject)
+            .validate()
+            .is_err());
+        // 7 minutes slot can't be aligned every day
+        assert!(make_policy(7, true, SlotAdjustment::Reject)
+            .validate()
+            .is_err());
+        assert!(make_policy(7, false, SlotAdjustment::Reject)
+            .validate()
+            .is_ok());
+
+        let mut p = make_policy(15, true, SlotAdjustment::Reject);
+        let mut q = p.clone();
+        q.min_duration_minutes = 60;
+        q.max_duration_minutes = 30;
+        assert!(q.validate().is_err());
+        q.max_duration_minutes = 0;
+        assert!(q.validate().is_ok());
+        q.max_advance_minutes = -1;
+        assert!(q.validate().is_err());
+
+        p.adjustment = 42;
+        assert!(p.validate().is_err());
+        p.resource_id = "".to_string();
+        assert_eq!(p.validate(), Err(Error::InvalidResourceId("".to_string())));
+    }
+
+    #[test]
+    fn duration_and_advance_should_be_checked() {
+        let policy = ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            min_duration_minutes: 15,
+            max_duration_minutes: 4 * 60,
+            min_advance_minutes: 60,
+            max_advance_minutes: 30 * 24 * 60,
+            ..Default::default()
+        };
+        let now = "2023-01-01T08:00:00+0800".parse().unwrap();
+
+        let r = rsvp("2023-01-01T10:00:00+0800", "2023-01-01T11:00:00+0800");
+        assert!(policy.check_duration(&r, now).is_ok());
+
+        let r = rsvp("2023-01-01T10:00:00+0800", "2023-01-01T10:00:05+0800");
+        assert_eq!(
+            policy.check_duration(&r, now),
+            Err(Error::DurationTooShort(15))
+        );
+
+        let r = rsvp("2023-01-01T10:00:00+0800", "2026-01-01T10:00:00+0800");
+        assert_eq!(
+            policy.check_duration(&r, now),
+            Err(Error::DurationTooLong(4 * 60))
+        );
+
+        let r = Reservation::new_open_ended(
+            "yangid",
+            "room-1",
+            "2023-01-01T10:00:00+0800".parse().unwrap(),
+            "",
+        );
+        assert_eq!(
+            policy.check_duration(&r, now),
+            Err(Error::DurationTooLong(4 * 60))
+        );
+
+        let r = rsvp("2023-01-01T08:30:00+0800", "2023-01-01T09:00:00+0800");
+        assert_eq!(policy.check_duration(&r, now), Err(Error::StartTooSoon(60)));
+
+        let r = rsvp("2023-03-01T10:00:00+0800", "2023-03-01T11:00:00+0800");
+        assert_eq!(
+            policy.check_duration(&r, now),
+            Err(Error::StartTooFarAhead(30 * 24 * 60))
+        );
+
+        // no restriction at all
+        let r = rsvp("2020-01-01T10:00:00+0800", "2026-01-01T10:00:00+0800");
+        assert!(ResourcePolicy::unrestricted("room-1")
+            .check_duration(&r, now)
+            .is_ok());
+    }
+}
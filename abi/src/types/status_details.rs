@@ -0,0 +1,102 @@
+use prost::Message;
+use prost_types::Any;
+
+use crate::{Reservation, ReservationAlternatives};
+
+const TYPE_URL_PREFIX: &str = "type.googleapis.com/";
+
+// google.rpc.Status, the standard content of grpc-status-details-bin. code and message are the same
+// as the grpc status, the payloads are in details as Any
+#[derive(Clone, PartialEq, Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<Any>,
+}
+
+// a message which can be sent in the details of a grpc status, told apart by its type url
+pub trait StatusDetail: Message + Default + Sized {
+    // full protobuf name of the message
+    const TYPE_NAME: &'static str;
+
+    fn type_url() -> String {
+        format!("{}{}", TYPE_URL_PREFIX, Self::TYPE_NAME)
+    }
+
+    // keep the code, message and metadata of the status, self becomes its only detail
+    fn attach_to_status(&self, status: tonic::Status) -> tonic::Status {
+        let details = RpcStatus {
+            code: status.code() as i32,
+            message: status.message().to_string(),
+            details: vec![Any {
+                type_url: Self::type_url(),
+                value: self.encode_to_vec(),
+            }],
+        };
+        tonic::Status::with_details_and_metadata(
+            status.code(),
+            status.message(),
+            details.encode_to_vec().into(),
+            status.metadata().clone(),
+        )
+    }
+
+    // the first detail of this type, None if the status has none
+    fn from_status_details(status: &tonic::Status) -> Option<Self> {
+        let details = RpcStatus::decode(status.details()).ok()?;
+        let type_url = Self::type_url();
+        details
+            .details
+            .iter()
+            .find(|any| any.type_url == type_url)
+            .and_then(|any| Self::decode(any.value.as_slice()).ok())
+    }
+}
+
+impl StatusDetail for Reservation {
+    const TYPE_NAME: &'static str = "reservation.Reservation";
+}
+
+impl StatusDetail for ReservationAlternatives {
+    const TYPE_NAME: &'static str = "reservation.ReservationAlternatives";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn details_should_be_a_google_rpc_status() {
+        let alternatives = ReservationAlternatives {
+            alternatives: vec![Reservation {
+                id: 1,
+                ..Default::default()
+            }],
+        };
+        let status = alternatives.attach_to_status(tonic::Status::not_found("taken"));
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(status.message(), "taken");
+
+        let details = RpcStatus::decode(status.details()).unwrap();
+        assert_eq!(details.code, tonic::Code::NotFound as i32);
+        assert_eq!(details.message, "taken");
+        assert_eq!(
+            details.details[0].type_url,
+            "type.googleapis.com/reservation.ReservationAlternatives"
+        );
+
+        assert_eq!(
+            ReservationAlternatives::from_status_details(&status),
+            Some(alternatives)
+        );
+        assert_eq!(
+            ReservationAlternatives::from_status_details(&tonic::Status::not_found("")),
+            None
+        );
+        // the type url tells the payloads apart
+        assert_eq!(Reservation::from_status_details(&status), None);
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use sqlx::postgres::types::PgRange;
+
+use crate::{convert_timestamp_into_window_pgrange, validate_window, Error, TimeWindow, Validator};
+
+impl TimeWindow {
+    pub fn get_range(&self) -> Result<PgRange<DateTime<Utc>>, Error> {
+        convert_timestamp_into_window_pgrange(self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+impl Validator for TimeWindow {
+    fn validate(&self) -> Result<(), Error> {
+        validate_window(self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+// not set means no filter
+impl Validator for Option<TimeWindow> {
+    fn validate(&self) -> Result<(), Error> {
+        self.as_ref().map_or(Ok(()), |w| w.validate())
+    }
+}
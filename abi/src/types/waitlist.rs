@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use sqlx::{
+    postgres::{types::PgRange, PgRow},
+    FromRow, Row,
+};
+
+use super::reservation::NativeRange;
+use crate::{
+    convert_time_to_utc, convert_to_timestamp, Error, Reservation, ReservationStatus, Validator,
+    WaitlistEntry,
+};
+
+impl WaitlistEntry {
+    pub fn new(rsvp: Reservation) -> Self {
+        Self {
+            reservation: Some(rsvp),
+            ..Default::default()
+        }
+    }
+
+    // the reservation made when promoted, always pending
+    pub fn to_reservation(&self) -> Reservation {
+        Reservation {
+            id: 0,
+            status: ReservationStatus::Pending as i32,
+            ..self.reservation.clone().unwrap_or_default()
+        }
+    }
+}
+
+impl Validator for WaitlistEntry {
+    fn validate(&self) -> Result<(), Error> {
+        // missing reservation fails on the empty user id
+        self.to_reservation().validate()?;
+        if let Some(expires_at) = self.expires_at.as_ref() {
+            convert_time_to_utc(expires_at)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for WaitlistEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let range: PgRange<DateTime<Utc>> = row.try_get("timespan")?;
+        let range: NativeRange<DateTime<Utc>> = range.into();
+        let expires_at: Option<DateTime<Utc>> = row.try_get("expires_at")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            reservation: Some(Reservation {
+                id: 0,
+                user_id: row.try_get("user_id")?,
+                status: ReservationStatus::Pending as i32,
+                resource_id: row.try_get("resource_id")?,
+                start: range.start.map(convert_to_timestamp),
+                end: range.end.map(convert_to_timestamp),
+                note: row
+                    .try_get::<Option<String>, _>("note")?
+                    .unwrap_or_default(),
+                created_by: row.try_get("created_by")?,
+                ..Default::default()
+            }),
+            expires_at: expires_at.map(convert_to_timestamp),
+            reservation_id: row
+                .try_get::<Option<i64>, _>("reservation_id")?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waitlist_entry_should_be_validated() {
+        let rsvp = Reservation::new_pending(
+            "yangid",
+            "room-1",
+            "2030-01-07T10:00:00+0000".parse().unwrap(),
+            "2030-01-07T11:00:00+0000".parse().unwrap(),
+            "",
+        );
+        let entry = WaitlistEntry::new(rsvp.clone());
+        assert!(entry.validate().is_ok());
+        assert_eq!(entry.to_reservation(), rsvp);
+
+        assert_eq!(
+            WaitlistEntry::default().validate(),
+            Err(Error::InvalidUserId("".to_string()))
+        );
+        let entry = WaitlistEntry {
+            expires_at: Some(prost_types::Timestamp {
+                seconds: i64::MAX,
+                nanos: 0,
+            }),
+            ..WaitlistEntry::new(rsvp)
+        };
+        assert_eq!(entry.validate(), Err(Error::InvalidTime));
+    }
+}
@@ -1,10 +1,14 @@
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use prost_types::Timestamp;
 
-pub fn convert_time_to_utc(ts: &Timestamp) -> DateTime<Utc> {
+use crate::Error;
+
+// Timestamp comes from the client, seconds or nanos may be out of range for chrono
+pub fn convert_time_to_utc(ts: &Timestamp) -> Result<DateTime<Utc>, Error> {
+    let nanos = u32::try_from(ts.nanos).map_err(|_| Error::InvalidTime)?;
     let naive_dt =
-        NaiveDateTime::from_timestamp_opt(ts.seconds, ts.nanos as _).expect("Invalid timestamp");
-    Utc.from_utc_datetime(&naive_dt)
+        NaiveDateTime::from_timestamp_opt(ts.seconds, nanos).ok_or(Error::InvalidTime)?;
+    Ok(Utc.from_utc_datetime(&naive_dt))
 }
 
 pub fn convert_to_timestamp(dt: DateTime<Utc>) -> Timestamp {
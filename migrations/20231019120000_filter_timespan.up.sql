@@ -0,0 +1,71 @@
+-- 讓 timespan 可以單獨透過 GiST index 做範圍查詢 (reservations_conflict 的 index 是以 resource_id 開頭)
+CREATE INDEX reservations_timespan_idx ON rsvp.reservations USING gist (timespan);
+
+-- 多了 _start/_end 兩個參數，所以要先把舊的 rsvp.filter drop 掉，否則會變成 overload
+DROP FUNCTION rsvp.filter;
+
+-- 與 rsvp.query 相同的時間語意：_start 為 null 時用 -infinity，_end 為 null 時用 infinity
+CREATE OR REPLACE FUNCTION rsvp.filter(
+    uid text,
+    rid text,
+    status rsvp.reservation_status,
+    cursor bigint DEFAULT null,
+    is_desc bool DEFAULT FALSE,
+    page_size bigint DEFAULT 10,
+    _start timestamp with time zone DEFAULT null,
+    _end timestamp with time zone DEFAULT null
+) RETURNS TABLE (LIKE rsvp.reservations) AS $$
+DECLARE
+    _during tstzrange;
+    _sql text;
+BEGIN
+    _during := tstzrange(
+        COALESCE(_start, '-infinity'),
+        COALESCE(_end, 'infinity'),
+        '[)'
+    );
+
+    -- if the cursor is null, set it to 0, if is_desc is false, or to 2^64 - 1 if is_desc is true
+    -- initialize the cursor
+    IF cursor IS NULL OR cursor < 0 THEN
+        IF is_desc THEN
+            cursor := 9223372036854775807;
+        ELSE
+            cursor := 0;
+        END IF;
+    END IF;
+
+    -- if page_size is not between 10 and 100, set it to 10
+    IF page_size < 10 OR page_size > 100 THEN
+        page_size := 10;
+    END IF;
+
+    -- format the query based on parameters
+    _sql := format(
+        'SELECT * FROM rsvp.reservations WHERE %s AND %L @> timespan AND status = %L AND %s ORDER BY id %s LIMIT %L::integer',
+        CASE
+            WHEN is_desc THEN 'id <= ' || cursor
+            ELSE 'id >= ' || cursor
+        END,
+        _during,
+        status,
+        CASE
+            WHEN uid IS NULL AND rid IS NULL THEN 'TRUE'
+            WHEN uid IS NULL THEN 'resource_id = ' || quote_literal(rid)
+            WHEN rid IS NULL THEN 'user_id = ' || quote_literal(uid)
+            ELSE 'user_id = ' || quote_literal(uid) || ' AND resource_id = ' || quote_literal(rid)
+        END,
+        CASE
+            WHEN is_desc THEN 'DESC'
+            ELSE 'ASC'
+        END,
+        page_size + 1
+    );
+
+    -- log the sql
+    RAISE NOTICE '%', _sql;
+
+    -- execute the query
+    RETURN QUERY EXECUTE _sql;
+END;
+$$ LANGUAGE plpgsql;
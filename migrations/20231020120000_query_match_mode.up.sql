@@ -0,0 +1,54 @@
+-- 多了 match_mode 參數，先 drop 舊的 rsvp.query 避免 overload
+DROP FUNCTION rsvp.query;
+
+-- match_mode 決定 timespan 與查詢區間 _during 的比對方式:
+--   'overlaps'  : timespan && _during (只要有重疊)
+--   'contained' : _during @> timespan (完全落在區間內，原本的行為)
+--   'contains'  : timespan @> _during (完全包住整個區間)
+CREATE OR REPLACE FUNCTION rsvp.query(
+    uid text,
+    rid text,
+    _start timestamp with time zone,
+    _end timestamp with time zone,
+    status rsvp.reservation_status DEFAULT 'pending',
+    is_desc bool DEFAULT FALSE,
+    match_mode text DEFAULT 'contained'
+) RETURNS TABLE (LIKE rsvp.reservations) AS $$
+DECLARE
+    _during tstzrange;
+    _sql text;
+BEGIN
+    _during := tstzrange(
+        COALESCE(_start, '-infinity'),
+        COALESCE(_end, 'infinity'),
+        '[)'
+    );
+
+    -- format the query based on parameters
+    _sql := format(
+        'SELECT * FROM rsvp.reservations WHERE %s AND status = %L AND %s ORDER BY lower(timespan) %s',
+        CASE
+            WHEN match_mode = 'overlaps' THEN 'timespan && ' || quote_literal(_during)
+            WHEN match_mode = 'contains' THEN 'timespan @> ' || quote_literal(_during)
+            ELSE quote_literal(_during) || ' @> timespan'
+        END,
+        status,
+        CASE
+            WHEN uid IS NULL AND rid IS NULL THEN 'TRUE'
+            WHEN uid IS NULL THEN 'resource_id = ' || quote_literal(rid)
+            WHEN rid IS NULL THEN 'user_id = ' || quote_literal(uid)
+            ELSE 'user_id = ' || quote_literal(uid) || ' AND resource_id = ' || quote_literal(rid)
+        END,
+        CASE
+            WHEN is_desc THEN 'DESC'
+            ELSE 'ASC'
+        END
+    );
+
+    -- log the sql
+    RAISE NOTICE '%', _sql;
+
+    -- execute the query
+    RETURN QUERY EXECUTE _sql;
+END;
+$$ LANGUAGE plpgsql;
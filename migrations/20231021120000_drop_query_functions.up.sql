@@ -0,0 +1,4 @@
+-- rsvp.query / rsvp.filter 改由 reservation::manager 用 QueryBuilder 組出參數化的 sql，
+-- 不再需要用 format() + EXECUTE 動態組 sql (無法 cache query plan 且每次都 RAISE NOTICE)
+DROP FUNCTION rsvp.query;
+DROP FUNCTION rsvp.filter;
@@ -0,0 +1,3 @@
+DROP TRIGGER reservations_normalize_timespan ON rsvp.reservations;
+DROP FUNCTION rsvp.reservations_normalize_timespan();
+DROP FUNCTION rsvp.normalize_timespan(TSTZRANGE);
@@ -0,0 +1,29 @@
+-- open-ended reservations are stored as unbounded ranges, e.g. [start,)
+-- 'infinity' / '-infinity' can't be decoded by the client, so normalize them to unbounded
+CREATE OR REPLACE FUNCTION rsvp.normalize_timespan(ts TSTZRANGE) RETURNS TSTZRANGE AS $$
+BEGIN
+    IF isempty(ts) THEN
+        RETURN ts;
+    END IF;
+    RETURN tstzrange(
+        CASE WHEN isfinite(lower(ts)) THEN lower(ts) END,
+        CASE WHEN isfinite(upper(ts)) THEN upper(ts) END,
+        CASE WHEN lower_inc(ts) AND isfinite(lower(ts)) THEN '[' ELSE '(' END ||
+        CASE WHEN upper_inc(ts) AND isfinite(upper(ts)) THEN ']' ELSE ')' END
+    );
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_normalize_timespan() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.timespan := rsvp.normalize_timespan(NEW.timespan);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reservations_normalize_timespan
+    BEFORE INSERT OR UPDATE OF timespan ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.reservations_normalize_timespan();
+
+UPDATE rsvp.reservations SET timespan = rsvp.normalize_timespan(timespan)
+    WHERE NOT isfinite(lower(timespan)) OR NOT isfinite(upper(timespan));
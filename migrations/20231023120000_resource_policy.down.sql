@@ -0,0 +1,2 @@
+DROP TABLE rsvp.resource_policies;
+DROP TYPE rsvp.slot_adjustment;
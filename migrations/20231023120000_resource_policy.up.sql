@@ -0,0 +1,12 @@
+CREATE TYPE rsvp.slot_adjustment AS ENUM ('unknown', 'reject', 'snap');
+
+-- booking policy per resource, resource without a row has no restriction
+CREATE TABLE rsvp.resource_policies (
+    resource_id VARCHAR(64) NOT NULL,
+    slot_minutes INT NOT NULL DEFAULT 0,
+    align_to_slot BOOLEAN NOT NULL DEFAULT FALSE,
+    adjustment rsvp.slot_adjustment NOT NULL DEFAULT 'reject',
+
+    CONSTRAINT resource_policies_pkey PRIMARY KEY (resource_id),
+    CONSTRAINT resource_policies_slot_minutes_check CHECK (slot_minutes >= 0)
+);
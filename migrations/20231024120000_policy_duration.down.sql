@@ -0,0 +1,6 @@
+ALTER TABLE rsvp.resource_policies
+    DROP CONSTRAINT resource_policies_duration_check,
+    DROP COLUMN min_duration_minutes,
+    DROP COLUMN max_duration_minutes,
+    DROP COLUMN min_advance_minutes,
+    DROP COLUMN max_advance_minutes;
@@ -0,0 +1,10 @@
+-- 0 means no restriction
+ALTER TABLE rsvp.resource_policies
+    ADD COLUMN min_duration_minutes INT NOT NULL DEFAULT 0,
+    ADD COLUMN max_duration_minutes INT NOT NULL DEFAULT 0,
+    ADD COLUMN min_advance_minutes INT NOT NULL DEFAULT 0,
+    ADD COLUMN max_advance_minutes INT NOT NULL DEFAULT 0,
+    ADD CONSTRAINT resource_policies_duration_check CHECK (
+        min_duration_minutes >= 0 AND max_duration_minutes >= 0
+        AND min_advance_minutes >= 0 AND max_advance_minutes >= 0
+    );
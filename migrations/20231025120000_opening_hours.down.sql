@@ -0,0 +1,5 @@
+DROP TABLE rsvp.holidays;
+DROP TABLE rsvp.opening_hours;
+ALTER TABLE rsvp.resource_policies
+    DROP COLUMN timezone,
+    DROP COLUMN holiday_calendars;
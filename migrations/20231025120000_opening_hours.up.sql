@@ -0,0 +1,24 @@
+ALTER TABLE rsvp.resource_policies
+    ADD COLUMN timezone TEXT NOT NULL DEFAULT '',
+    ADD COLUMN holiday_calendars TEXT[] NOT NULL DEFAULT '{}';
+
+-- weekly opening hours in the local time of the policy, resource without rows is always open
+CREATE TABLE rsvp.opening_hours (
+    resource_id VARCHAR(64) NOT NULL REFERENCES rsvp.resource_policies (resource_id) ON DELETE CASCADE,
+    weekday INT NOT NULL, -- ISO weekday, 1 for Monday
+    open_minute INT NOT NULL,
+    close_minute INT NOT NULL,
+
+    CONSTRAINT opening_hours_check CHECK (
+        weekday BETWEEN 1 AND 7 AND open_minute >= 0 AND open_minute < close_minute AND close_minute <= 1440
+    )
+);
+CREATE INDEX opening_hours_resource_id_idx ON rsvp.opening_hours (resource_id);
+
+CREATE TABLE rsvp.holidays (
+    calendar_id VARCHAR(64) NOT NULL,
+    day DATE NOT NULL,
+    name TEXT NOT NULL DEFAULT '',
+
+    CONSTRAINT holidays_pkey PRIMARY KEY (calendar_id, day)
+);
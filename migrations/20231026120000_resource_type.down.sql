@@ -0,0 +1 @@
+ALTER TABLE rsvp.resource_policies DROP COLUMN resource_type;
@@ -0,0 +1,2 @@
+ALTER TABLE rsvp.resource_policies ADD COLUMN resource_type VARCHAR(64) NOT NULL DEFAULT '';
+CREATE INDEX resource_policies_resource_type_idx ON rsvp.resource_policies (resource_type);
@@ -0,0 +1,2 @@
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_user_conflict;
+ALTER TABLE rsvp.reservations DROP COLUMN user_exclusion;
@@ -0,0 +1,4 @@
+-- set to the resource type when the type is configured as exclusive per user, NULL never conflicts
+ALTER TABLE rsvp.reservations ADD COLUMN user_exclusion VARCHAR(64);
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_user_conflict
+    EXCLUDE USING gist (user_id WITH =, user_exclusion WITH =, timespan WITH &&);
@@ -0,0 +1,7 @@
+DROP FUNCTION rsvp.refresh_claims(VARCHAR);
+DROP TRIGGER reservation_claims_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.reservation_claims_trigger();
+DROP FUNCTION rsvp.claim_units(BIGINT);
+DROP TABLE rsvp.reservation_claims;
+DROP FUNCTION rsvp.resource_units(VARCHAR);
+DROP TABLE rsvp.resource_members;
@@ -0,0 +1,72 @@
+-- a composite resource made of other resources, members can be groups too
+CREATE TABLE rsvp.resource_members (
+    group_id VARCHAR(64) NOT NULL,
+    member_id VARCHAR(64) NOT NULL,
+
+    CONSTRAINT resource_members_pkey PRIMARY KEY (group_id, member_id),
+    CONSTRAINT resource_members_check CHECK (group_id <> member_id)
+);
+CREATE INDEX resource_members_member_id_idx ON rsvp.resource_members (member_id);
+
+-- the resource itself and all its members, recursively
+CREATE OR REPLACE FUNCTION rsvp.resource_units(rid VARCHAR(64)) RETURNS TABLE (resource_id VARCHAR(64)) AS $$
+    WITH RECURSIVE units(id) AS (
+        SELECT rid
+        UNION
+        SELECT m.member_id FROM rsvp.resource_members m JOIN units u ON m.group_id = u.id
+    )
+    SELECT id FROM units;
+$$ LANGUAGE sql STABLE;
+
+-- every reservation claims all units of its resource, so a group and its members exclude each other
+CREATE TABLE rsvp.reservation_claims (
+    reservation_id BIGINT NOT NULL REFERENCES rsvp.reservations (id) ON DELETE CASCADE,
+    resource_id VARCHAR(64) NOT NULL,
+    timespan TSTZRANGE NOT NULL,
+
+    CONSTRAINT reservation_claims_pkey PRIMARY KEY (reservation_id, resource_id),
+    CONSTRAINT reservation_claims_conflict EXCLUDE USING gist (resource_id WITH =, timespan WITH &&)
+);
+
+CREATE OR REPLACE FUNCTION rsvp.claim_units(rsvp_id BIGINT) RETURNS VOID AS $$
+BEGIN
+    DELETE FROM rsvp.reservation_claims WHERE reservation_id = rsvp_id;
+    INSERT INTO rsvp.reservation_claims (reservation_id, resource_id, timespan)
+        SELECT r.id, u.resource_id, r.timespan
+        FROM rsvp.reservations r, rsvp.resource_units(r.resource_id) u
+        WHERE r.id = rsvp_id;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION rsvp.reservation_claims_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM rsvp.claim_units(NEW.id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reservation_claims_trigger
+    AFTER INSERT OR UPDATE OF resource_id, timespan ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.reservation_claims_trigger();
+
+-- members of a group changed, claim again for the reservations which are not over yet
+CREATE OR REPLACE FUNCTION rsvp.refresh_claims(gid VARCHAR(64)) RETURNS VOID AS $$
+DECLARE
+    rsvp_id BIGINT;
+BEGIN
+    FOR rsvp_id IN
+        WITH RECURSIVE groups(id) AS (
+            SELECT gid
+            UNION
+            SELECT m.group_id FROM rsvp.resource_members m JOIN groups g ON m.member_id = g.id
+        )
+        SELECT r.id FROM rsvp.reservations r JOIN groups g ON r.resource_id = g.id
+        WHERE upper_inf(r.timespan) OR upper(r.timespan) > now()
+    LOOP
+        PERFORM rsvp.claim_units(rsvp_id);
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+INSERT INTO rsvp.reservation_claims (reservation_id, resource_id, timespan)
+    SELECT id, resource_id, timespan FROM rsvp.reservations;
@@ -0,0 +1,2 @@
+DROP FUNCTION rsvp.resource_subtree(VARCHAR);
+DROP TABLE rsvp.resources;
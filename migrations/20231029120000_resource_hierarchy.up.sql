@@ -0,0 +1,19 @@
+-- location tree of the resources, e.g. campus > building > floor > room
+CREATE TABLE rsvp.resources (
+    id VARCHAR(64) NOT NULL,
+    parent_id VARCHAR(64) REFERENCES rsvp.resources (id),
+
+    CONSTRAINT resources_pkey PRIMARY KEY (id),
+    CONSTRAINT resources_check CHECK (id <> parent_id)
+);
+CREATE INDEX resources_parent_id_idx ON rsvp.resources (parent_id);
+
+-- the root itself and everything under it
+CREATE OR REPLACE FUNCTION rsvp.resource_subtree(root VARCHAR(64)) RETURNS TABLE (resource_id VARCHAR(64)) AS $$
+    WITH RECURSIVE subtree(id) AS (
+        SELECT root
+        UNION
+        SELECT r.id FROM rsvp.resources r JOIN subtree s ON r.parent_id = s.id
+    )
+    SELECT id FROM subtree;
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1 @@
+DROP TABLE rsvp.resource_attributes;
@@ -0,0 +1,9 @@
+-- metadata of a resource used to search available resources, e.g. capacity, projector
+CREATE TABLE rsvp.resource_attributes (
+    resource_id VARCHAR(64) NOT NULL REFERENCES rsvp.resources (id) ON DELETE CASCADE,
+    key VARCHAR(64) NOT NULL,
+    value TEXT NOT NULL,
+
+    CONSTRAINT resource_attributes_pkey PRIMARY KEY (resource_id, key)
+);
+CREATE INDEX resource_attributes_key_value_idx ON rsvp.resource_attributes (key, value);
@@ -0,0 +1,2 @@
+DROP TABLE rsvp.waitlist;
+-- postgres can't drop an enum value, 'promote' stays in rsvp.reservation_update_type
@@ -0,0 +1,17 @@
+ALTER TYPE rsvp.reservation_update_type ADD VALUE 'promote';
+
+-- requests waiting for a taken range, promoted first come first served
+CREATE TABLE rsvp.waitlist (
+    id BIGSERIAL NOT NULL,
+    user_id VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(64) NOT NULL,
+    timespan TSTZRANGE NOT NULL,
+    note TEXT,
+    expires_at TIMESTAMPTZ,
+    -- set when promoted
+    reservation_id BIGINT REFERENCES rsvp.reservations (id) ON DELETE SET NULL,
+    promoted BOOLEAN NOT NULL DEFAULT false,
+
+    CONSTRAINT waitlist_pkey PRIMARY KEY (id)
+);
+CREATE INDEX waitlist_waiting_idx ON rsvp.waitlist USING gist (resource_id, timespan) WHERE NOT promoted;
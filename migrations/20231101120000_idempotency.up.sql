@@ -0,0 +1,13 @@
+-- responses of mutating requests with an idempotency key, replayed on retry
+CREATE TABLE rsvp.idempotency_keys (
+    method VARCHAR(32) NOT NULL,
+    key TEXT NOT NULL,
+    -- the request without its key, a retry must send the same request
+    fingerprint BYTEA NOT NULL,
+    -- NULL while the request is in progress
+    response BYTEA,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+    CONSTRAINT idempotency_keys_pkey PRIMARY KEY (method, key)
+);
+CREATE INDEX idempotency_keys_created_at_idx ON rsvp.idempotency_keys (created_at);
@@ -0,0 +1,3 @@
+DROP TRIGGER reservations_version_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.bump_reservation_version();
+ALTER TABLE rsvp.reservations DROP COLUMN version;
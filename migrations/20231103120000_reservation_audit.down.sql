@@ -0,0 +1,14 @@
+DROP TRIGGER reservations_creator_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.default_reservation_creator();
+
+CREATE OR REPLACE FUNCTION rsvp.bump_reservation_version() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE rsvp.reservations
+    DROP COLUMN created_at,
+    DROP COLUMN updated_at,
+    DROP COLUMN created_by;
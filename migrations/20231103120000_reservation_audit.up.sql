@@ -0,0 +1,32 @@
+-- when and by whom a reservation is made, existing reservations are treated as made by their user now
+ALTER TABLE rsvp.reservations
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN created_by VARCHAR(64);
+UPDATE rsvp.reservations SET created_by = user_id;
+ALTER TABLE rsvp.reservations ALTER COLUMN created_by SET NOT NULL;
+
+CREATE INDEX reservations_created_at_idx ON rsvp.reservations (created_at, id);
+CREATE INDEX reservations_updated_at_idx ON rsvp.reservations (updated_at, id);
+CREATE INDEX reservations_created_by_idx ON rsvp.reservations (created_by);
+
+-- rows inserted without a creator are made by their user
+CREATE OR REPLACE FUNCTION rsvp.default_reservation_creator() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.created_by := COALESCE(NULLIF(NEW.created_by, ''), NEW.user_id);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reservations_creator_trigger
+    BEFORE INSERT ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.default_reservation_creator();
+
+-- updated_at goes with the version
+CREATE OR REPLACE FUNCTION rsvp.bump_reservation_version() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    NEW.updated_at := now();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,41 @@
+CREATE TYPE rsvp.delegation_action AS ENUM ('book', 'confirm', 'cancel');
+
+-- owner lets delegate do the action on the owner's reservations
+CREATE TABLE rsvp.delegations (
+    owner_id VARCHAR(64) NOT NULL,
+    delegate_id VARCHAR(64) NOT NULL,
+    action rsvp.delegation_action NOT NULL,
+
+    CONSTRAINT delegations_pkey PRIMARY KEY (owner_id, delegate_id, action),
+    CONSTRAINT delegations_not_self CHECK (owner_id <> delegate_id)
+);
+
+-- both the owner and who did the change, NULL for changes recorded before
+ALTER TABLE rsvp.reservation_changes
+    ADD COLUMN user_id VARCHAR(64),
+    ADD COLUMN acted_by VARCHAR(64);
+
+-- the manager sets rsvp.acted_by for the transaction when someone acts for the owner,
+-- otherwise the booker made it and the owner changed it
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    actor VARCHAR(64) := NULLIF(current_setting('rsvp.acted_by', true), '');
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, user_id, acted_by)
+            VALUES (NEW.id, 'create', NEW.user_id, COALESCE(actor, NEW.created_by));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status changed, update reservation_changes
+        IF OLD.status <> NEW.status THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, user_id, acted_by)
+                VALUES (NEW.id, 'update', NEW.user_id, COALESCE(actor, NEW.user_id));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, user_id, acted_by)
+            VALUES (OLD.id, 'delete', OLD.user_id, COALESCE(actor, OLD.user_id));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,22 @@
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    actor VARCHAR(64) := NULLIF(current_setting('rsvp.acted_by', true), '');
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, user_id, acted_by)
+            VALUES (NEW.id, 'create', NEW.user_id, COALESCE(actor, NEW.created_by));
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status changed, update reservation_changes
+        IF OLD.status <> NEW.status THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, user_id, acted_by)
+                VALUES (NEW.id, 'update', NEW.user_id, COALESCE(actor, NEW.user_id));
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, user_id, acted_by)
+            VALUES (OLD.id, 'delete', OLD.user_id, COALESCE(actor, OLD.user_id));
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1 @@
+ALTER TABLE rsvp.idempotency_keys DROP COLUMN locked_until;
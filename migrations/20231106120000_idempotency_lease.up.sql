@@ -0,0 +1,3 @@
+-- a key in progress is held until locked_until, the server renews it while the request is running.
+-- if the server dies the lease runs out and the key can be claimed again
+ALTER TABLE rsvp.idempotency_keys ADD COLUMN locked_until TIMESTAMPTZ NOT NULL DEFAULT now();
@@ -0,0 +1 @@
+ALTER TABLE rsvp.waitlist DROP COLUMN created_by;
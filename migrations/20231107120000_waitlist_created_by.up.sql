@@ -0,0 +1,4 @@
+-- who joined the waitlist, the booker of the reservation made when the entry is promoted
+ALTER TABLE rsvp.waitlist ADD COLUMN created_by VARCHAR(64);
+UPDATE rsvp.waitlist SET created_by = user_id;
+ALTER TABLE rsvp.waitlist ALTER COLUMN created_by SET NOT NULL;
@@ -0,0 +1,180 @@
+// compare the old plpgsql rsvp.query/rsvp.filter (dynamic sql through EXECUTE) with the
+// parameterised sql built by ReservationManager, each iteration fires CONCURRENCY requests at once.
+// needs the postgres in service/fixtures/config.yml, run with `cargo bench -p reservation`
+
+use abi::{Config, FilterByIdBuilder, Reservation, ReservationQueryBuilder, ReservationStatus};
+use criterion::{criterion_group, criterion_main, Criterion};
+use prost_types::Timestamp;
+use reservation::{ReservationManager, Rsvp};
+use sqlx::{postgres::PgPoolOptions, Connection, Executor, PgConnection, PgPool};
+use std::sync::Arc;
+use tokio::{runtime::Runtime, task::JoinSet};
+
+// the functions dropped by the migration, install them again so both sides run on the same data
+const LEGACY_FUNCTIONS: &str =
+    include_str!("../../migrations/20231021120000_drop_query_functions.down.sql");
+const CONCURRENCY: usize = 32;
+
+fn query_benchmark(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let config = rt.block_on(setup_database());
+    let pool = rt
+        .block_on(
+            PgPoolOptions::new()
+                .max_connections(CONCURRENCY as u32)
+                .connect(&config.db.database_url()),
+        )
+        .unwrap();
+    let manager = Arc::new(ReservationManager::new(pool.clone()));
+
+    let mut group = c.benchmark_group("query");
+    group.bench_function("plpgsql rsvp.query", |b| {
+        b.to_async(&rt).iter(|| {
+            run_concurrently(pool.clone(), |pool, i| async move {
+                sqlx::query_as::<_, Reservation>(
+                    "SELECT * FROM rsvp.query($1, $2, $3, $4, $5::rsvp.reservation_status, $6, $7)",
+                )
+                .bind(None::<String>)
+                .bind(Some(format!("room-{}", i)))
+                .bind(Some(parse_time("2023-01-01T00:00:00Z")))
+                .bind(Some(parse_time("2023-03-01T00:00:00Z")))
+                .bind("pending")
+                .bind(false)
+                .bind("contained")
+                .fetch_all(&pool)
+                .await
+                .unwrap()
+                .len()
+            })
+        })
+    });
+    group.bench_function("rust QueryBuilder", |b| {
+        b.to_async(&rt).iter(|| {
+            run_concurrently(manager.clone(), |manager, i| async move {
+                let query = ReservationQueryBuilder::default()
+                    .resource_id(format!("room-{}", i))
+                    .status(ReservationStatus::Pending as i32)
+                    .start("2023-01-01T00:00:00Z".parse::<Timestamp>().unwrap())
+                    .end("2023-03-01T00:00:00Z".parse::<Timestamp>().unwrap())
+                    .build()
+                    .unwrap();
+                let mut rx = manager.query(query).await;
+                let mut count = 0;
+                while let Some(rsvp) = rx.recv().await {
+                    rsvp.unwrap();
+                    count += 1;
+                }
+                count
+            })
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("filter");
+    group.bench_function("plpgsql rsvp.filter", |b| {
+        b.to_async(&rt).iter(|| {
+            run_concurrently(pool.clone(), |pool, i| async move {
+                sqlx::query_as::<_, Reservation>(
+                    "SELECT * FROM rsvp.filter($1, $2, $3::rsvp.reservation_status, $4, $5, $6, $7, $8)",
+                )
+                .bind(Some(format!("user-{}", i)))
+                .bind(None::<String>)
+                .bind("pending")
+                .bind(0_i64)
+                .bind(false)
+                .bind(100_i64)
+                .bind(None::<chrono::DateTime<chrono::Utc>>)
+                .bind(None::<chrono::DateTime<chrono::Utc>>)
+                .fetch_all(&pool)
+                .await
+                .unwrap()
+                .len()
+            })
+        })
+    });
+    group.bench_function("rust QueryBuilder", |b| {
+        b.to_async(&rt).iter(|| {
+            run_concurrently(manager.clone(), |manager, i| async move {
+                let filter = FilterByIdBuilder::default()
+                    .user_id(format!("user-{}", i))
+                    .status(ReservationStatus::Pending as i32)
+                    .page_size(100)
+                    .build()
+                    .unwrap();
+                manager.keyset_query(filter).await.unwrap().1.len()
+            })
+        })
+    });
+    group.finish();
+
+    rt.block_on(async {
+        pool.close().await;
+        cleanup_database(&config).await;
+    });
+}
+
+// spawn CONCURRENCY tasks and wait all of them, i is used to spread the load on different rows
+async fn run_concurrently<T, F, Fut>(shared: T, f: F)
+where
+    T: Clone + Send + 'static,
+    F: Fn(T, usize) -> Fut,
+    Fut: std::future::Future<Output = usize> + Send + 'static,
+{
+    let mut set = JoinSet::new();
+    for i in 0..CONCURRENCY {
+        set.spawn(f(shared.clone(), i));
+    }
+    while let Some(ret) = set.join_next().await {
+        ret.unwrap();
+    }
+}
+
+// create a new database, run migrations, install the legacy functions and seed 10000 reservations
+async fn setup_database() -> Config {
+    let mut config = Config::load("../service/fixtures/config.yml").unwrap();
+    config.db.dbname = format!("bench_reservation_{}", std::process::id());
+
+    let mut conn = PgConnection::connect(&config.db.server_url())
+        .await
+        .unwrap();
+    conn.execute(format!(r#"CREATE DATABASE "{}""#, config.db.dbname).as_str())
+        .await
+        .unwrap();
+    drop(conn);
+
+    let pool = PgPool::connect(&config.db.database_url()).await.unwrap();
+    sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+    pool.execute(LEGACY_FUNCTIONS).await.unwrap();
+
+    // 100 rooms, each room has 100 one-day reservations in a row
+    pool.execute(
+        "INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note)
+        SELECT 'user-' || (i % 50), 'room-' || (i % 100),
+            tstzrange('2023-01-01'::timestamptz + (i / 100) * interval '1 day',
+                '2023-01-01'::timestamptz + (i / 100 + 1) * interval '1 day'),
+            'bench'
+        FROM generate_series(0, 9999) AS i",
+    )
+    .await
+    .unwrap();
+    pool.execute("ANALYZE rsvp.reservations").await.unwrap();
+    pool.close().await;
+
+    config
+}
+
+async fn cleanup_database(config: &Config) {
+    let mut conn = PgConnection::connect(&config.db.server_url())
+        .await
+        .unwrap();
+    conn.execute(format!(r#"DROP DATABASE "{}" WITH (FORCE)"#, config.db.dbname).as_str())
+        .await
+        .unwrap();
+}
+
+fn parse_time(s: &str) -> chrono::DateTime<chrono::Utc> {
+    s.parse().unwrap()
+}
+
+criterion_group!(benches, query_benchmark);
+criterion_main!(benches);
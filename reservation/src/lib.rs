@@ -4,31 +4,134 @@ mod tests;
 use abi::{Error, FilterPager};
 use async_trait::async_trait;
 pub use manager::ReservationManager;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 type ReservationReceiver = mpsc::Receiver<Result<abi::Reservation, abi::Error>>;
 
 #[async_trait]
 pub trait Rsvp {
-    // make a reservation
+    // make a reservation, created_by is the booker and is required.
+    // booking for another user (created_by <> user_id) needs a BOOK delegation
     async fn reserve(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, Error>;
+    // the changes below fail with VersionMismatch if expected_version is given and the reservation has another version
     // change reservation status
-    async fn change_status(&self, id: abi::ReservationId) -> Result<abi::Reservation, Error>;
-    // update note
+    // acted_by is who confirms and is required, anyone but the owner needs a CONFIRM delegation
+    async fn change_status(
+        &self,
+        id: abi::ReservationId,
+        expected_version: Option<i64>,
+        acted_by: &str,
+    ) -> Result<abi::Reservation, Error>;
+    // move reservation to another time, the policy of the resource is checked again.
+    // acted_by is required, anyone but the owner needs a BOOK delegation
+    async fn reschedule(
+        &self,
+        id: abi::ReservationId,
+        start: Option<prost_types::Timestamp>,
+        end: Option<prost_types::Timestamp>,
+        expected_version: Option<i64>,
+        acted_by: &str,
+    ) -> Result<abi::Reservation, Error>;
+    // update note, same delegation as reschedule
     async fn update_note(
         &self,
         id: abi::ReservationId,
         note: String,
+        expected_version: Option<i64>,
+        acted_by: &str,
+    ) -> Result<abi::Reservation, Error>;
+    // delete reservation, acted_by is who cancels and is required. anyone but the owner needs a CANCEL delegation
+    async fn delete(
+        &self,
+        id: abi::ReservationId,
+        expected_version: Option<i64>,
+        acted_by: &str,
     ) -> Result<abi::Reservation, Error>;
-    // delete reservation
-    async fn delete(&self, id: abi::ReservationId) -> Result<abi::Reservation, Error>;
     // get reservation
     async fn get(&self, id: abi::ReservationId) -> Result<abi::Reservation, Error>;
     // get user's all reservation
     async fn query(&self, query_id: abi::ReservationQuery) -> ReservationReceiver;
+    // same as query, but the statement timeout is bounded by the given timeout (e.g. grpc deadline)
+    async fn query_with_timeout(
+        &self,
+        query_id: abi::ReservationQuery,
+        timeout: Option<Duration>,
+    ) -> ReservationReceiver;
     // query reservation order by reservation id
     async fn keyset_query(
         &self,
         filter: abi::FilterById,
     ) -> Result<(FilterPager, Vec<abi::Reservation>), Error>;
+    // create or replace the booking policy of a resource
+    async fn set_policy(&self, policy: abi::ResourcePolicy) -> Result<abi::ResourcePolicy, Error>;
+    // get the booking policy of a resource, unrestricted if not set
+    async fn get_policy(&self, rid: abi::ResourceId) -> Result<abi::ResourcePolicy, Error>;
+    // replace all holidays of a calendar
+    async fn set_holidays(
+        &self,
+        calendar: abi::HolidayCalendar,
+    ) -> Result<abi::HolidayCalendar, Error>;
+    // get all holidays of a calendar
+    async fn get_holidays(&self, calendar_id: String) -> Result<abi::HolidayCalendar, Error>;
+    // replace the members of a resource group, reservations on the group and its members conflict
+    async fn set_resource_group(
+        &self,
+        group: abi::ResourceGroup,
+    ) -> Result<abi::ResourceGroup, Error>;
+    // get the direct members of a resource group, empty if it's not a group
+    async fn get_resource_group(&self, group_id: String) -> Result<abi::ResourceGroup, Error>;
+    // replace what the delegate may do for the owner, no actions revokes it. acted_by must be the owner
+    async fn set_delegation(
+        &self,
+        delegation: abi::Delegation,
+        acted_by: &str,
+    ) -> Result<abi::Delegation, Error>;
+    // get what the delegate may do for the owner, no actions if not delegated
+    async fn get_delegation(
+        &self,
+        owner_id: String,
+        delegate_id: String,
+    ) -> Result<abi::Delegation, Error>;
+    // create or move a resource in the location tree
+    async fn set_resource(&self, resource: abi::Resource) -> Result<abi::Resource, Error>;
+    // get a resource in the location tree, a root if never set
+    async fn get_resource(&self, rid: abi::ResourceId) -> Result<abi::Resource, Error>;
+    // resources free and open in the window and matching all predicates, best fit first
+    async fn find_available(
+        &self,
+        request: abi::FindAvailableRequest,
+    ) -> Result<Vec<abi::Resource>, Error>;
+    // wait for a taken range, promoted at once if it's free already. created_by of the reservation is the
+    // booker as on reserve, and the booker of the reservation made when promoted
+    async fn join_waitlist(&self, entry: abi::WaitlistEntry) -> Result<abi::WaitlistEntry, Error>;
+    // remove an entry from the waitlist, acted_by is required. anyone but the owner needs a BOOK delegation
+    async fn leave_waitlist(&self, id: i64, acted_by: &str) -> Result<abi::WaitlistEntry, Error>;
+    // get a waitlist entry, reservation_id is set once promoted
+    async fn get_waitlist_entry(&self, id: i64) -> Result<abi::WaitlistEntry, Error>;
+    // nearest free windows of the same length when rsvp conflicts, optionally on resources of the same type
+    async fn suggest_alternatives(
+        &self,
+        rsvp: abi::Reservation,
+        include_similar: bool,
+    ) -> Result<Vec<abi::Reservation>, Error>;
+    // claim an idempotency key for a request, returns the stored response if the same request was done.
+    // fails if the key is used by a different request or the first request is still in progress
+    async fn claim_idempotency_key(
+        &self,
+        method: &str,
+        key: &str,
+        fingerprint: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, Error>;
+    // store the response of a claimed key, replayed until the key expires
+    async fn save_idempotency_response(
+        &self,
+        method: &str,
+        key: &str,
+        response: Vec<u8>,
+    ) -> Result<(), Error>;
+    // extend the lease of a claimed key whose request is still running
+    async fn renew_idempotency_key(&self, method: &str, key: &str) -> Result<(), Error>;
+    // release a claimed key when the request failed, so it can be retried with the same key
+    async fn release_idempotency_key(&self, method: &str, key: &str) -> Result<(), Error>;
 }
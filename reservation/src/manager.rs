@@ -1,17 +1,27 @@
 use crate::Rsvp;
-use abi::{convert_time_to_utc, DbConfig, Error, FilterPager, ReservationId, Validator};
+use abi::{
+    convert_time_to_utc, convert_timestamp_into_window_pgrange, convert_to_timestamp, DbConfig,
+    DelegationAction, Error, ExclusionConfig, FilterPager, IdempotencyConfig, QueryConfig,
+    QuotaConfig, QuotaUsage, ReservationConflict, ReservationConflictInfo, ReservationId,
+    Validator,
+};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use futures::StreamExt;
 use sqlx::{
     postgres::{types::PgRange, PgPoolOptions},
-    Either, PgPool, Row,
+    Either, FromRow, PgPool, Postgres, QueryBuilder, Row, Transaction,
 };
+use std::{collections::HashMap, future::Future, ops::Bound, time::Duration};
 use tokio::sync::mpsc;
 use tracing::{info, warn};
 
 pub struct ReservationManager {
     pool: PgPool, // sqlx 裡面 postgres pool database connection 使用Arc將各種database connection 分開
+    query_config: QueryConfig,
+    quota_config: QuotaConfig,
+    exclusion_config: ExclusionConfig,
+    idempotency_config: IdempotencyConfig,
 }
 
 // type alias for simplify type
@@ -20,73 +30,175 @@ type ReservationReceiver = mpsc::Receiver<RsvpResult>;
 
 #[async_trait]
 impl Rsvp for ReservationManager {
-    async fn reserve(&self, mut rsvp: abi::Reservation) -> RsvpResult {
-        rsvp.validate()?;
-
-        let status = abi::ReservationStatus::from_i32(rsvp.status)
-            .unwrap_or(abi::ReservationStatus::Pending);
-
-        let timespan: PgRange<DateTime<Utc>> = rsvp.get_timestamp();
-
-        let id:i64 = sqlx::query(
-            "INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status) VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status) RETURNING id"
-        )
-        .bind(rsvp.user_id.clone())
-        .bind(rsvp.resource_id.clone())
-        .bind(timespan)
-        .bind(rsvp.note.clone())
-        .bind(status.to_string())
-        .fetch_one(&self.pool)
-        .await?.get(0);
-
-        rsvp.id = id;
-
-        Ok(rsvp)
+    async fn reserve(&self, rsvp: abi::Reservation) -> RsvpResult {
+        self.insert_reservation(rsvp, None).await
     }
 
     // change reservation status
-    async fn change_status(&self, id: ReservationId) -> RsvpResult {
+    async fn change_status(
+        &self,
+        id: ReservationId,
+        expected_version: Option<i64>,
+        acted_by: &str,
+    ) -> RsvpResult {
         // error: code: "42883", message: "operator does not exist: uuid = text"，所以轉Uuid進去查詢語句。
         // let id: Uuid = Uuid::from_str(&id).map_err(|_| abi::Error::InvalidReservationId(id.clone()))?;
 
         id.validate()?;
+        self.check_acting_on(id, acted_by, DelegationAction::Confirm)
+            .await?;
 
-        // if current status is pending, change status into confirmed, otherwise do nothing
-        let rsvp = sqlx::query_as("UPDATE rsvp.reservations SET status = 'confirmed' WHERE id = $1 AND status = 'pending' RETURNING *
-        ")
+        // if current status is pending, change status into confirmed
+        let rsvp: Option<abi::Reservation> = retry_on_abort(|| async {
+            let mut tx = self.pool.begin().await?;
+            set_acted_by(&mut tx, acted_by).await?;
+            let rsvp = sqlx::query_as(
+                "UPDATE rsvp.reservations SET status = 'confirmed' WHERE id = $1 AND status = 'pending' AND ($2::bigint IS NULL OR version = $2) RETURNING *",
+            )
             .bind(id)
-            .fetch_one(&self.pool)
-            .await.map_err(|_|abi::Error::NotFound)?;
+            .bind(expected_version)
+            .fetch_optional(&mut tx)
+            .await?;
+            tx.commit().await?;
+            Ok(rsvp)
+        })
+        .await?;
 
-        Ok(rsvp)
+        if let Some(rsvp) = rsvp {
+            return Ok(rsvp);
+        }
+
+        // nothing updated, find out why: not exist, changed by others, already confirmed (idempotent) or other status
+        let rsvp = self.get(id).await?;
+        check_version(&rsvp, expected_version)?;
+        match abi::ReservationStatus::from_i32(rsvp.status) {
+            Some(abi::ReservationStatus::Confirmed) => Ok(rsvp),
+            status => Err(Error::NotPending(
+                status.unwrap_or(abi::ReservationStatus::Unknown),
+            )),
+        }
+    }
+
+    async fn reschedule(
+        &self,
+        id: ReservationId,
+        start: Option<prost_types::Timestamp>,
+        end: Option<prost_types::Timestamp>,
+        expected_version: Option<i64>,
+        acted_by: &str,
+    ) -> RsvpResult {
+        id.validate()?;
+
+        let mut rsvp = self.get(id).await?;
+        self.check_delegation(&rsvp.user_id, acted_by, DelegationAction::Book)
+            .await?;
+        check_version(&rsvp, expected_version)?;
+        let old = rsvp.clone();
+        rsvp.start = start;
+        rsvp.end = end;
+        rsvp.validate()?;
+        let policy = self.apply_policy(&mut rsvp).await?;
+
+        let timespan: PgRange<DateTime<Utc>> = rsvp.get_timestamp()?;
+        let user_exclusion = self.user_exclusion(&policy);
+
+        let ret = retry_on_abort(|| async {
+            let mut tx = self.pool.begin().await?;
+            set_acted_by(&mut tx, acted_by).await?;
+            self.check_quota(&mut tx, &rsvp, &policy).await?;
+            let rsvp = sqlx::query_as(
+                "UPDATE rsvp.reservations SET timespan = $1, user_exclusion = $2 WHERE id = $3 AND ($4::bigint IS NULL OR version = $4) RETURNING *",
+            )
+            .bind(timespan.clone())
+            .bind(user_exclusion.clone())
+            .bind(id)
+            .bind(expected_version)
+            .fetch_one(&mut tx)
+            .await?;
+            tx.commit().await?;
+            Ok(rsvp)
+        })
+        .await;
+        match ret {
+            Err(Error::NotFound) => Err(self.resolve_not_found(id, expected_version).await),
+            Err(e) => Err(self
+                .resolve_conflict(&rsvp, timespan, user_exclusion, e)
+                .await),
+            Ok(rsvp) => {
+                // the old range may be free now
+                self.promote_waitlist(&old).await;
+                Ok(rsvp)
+            }
+        }
     }
 
     // update note
-    async fn update_note(&self, id: ReservationId, note: String) -> RsvpResult {
+    async fn update_note(
+        &self,
+        id: ReservationId,
+        note: String,
+        expected_version: Option<i64>,
+        acted_by: &str,
+    ) -> RsvpResult {
         // let id = Uuid::from_str(&id).map_err(|_| abi::Error::InvalidReservationId(id.clone()))?;
 
         id.validate()?;
+        self.check_acting_on(id, acted_by, DelegationAction::Book)
+            .await?;
 
-        let rsvp =
-            sqlx::query_as("UPDATE rsvp.reservations SET note = $1 WHERE id = $2 RETURNING *")
-                .bind(note)
-                .bind(id)
-                .fetch_one(&self.pool)
-                .await?;
-        Ok(rsvp)
+        let ret = retry_on_abort(|| async {
+            let mut tx = self.pool.begin().await?;
+            set_acted_by(&mut tx, acted_by).await?;
+            let rsvp = sqlx::query_as(
+                "UPDATE rsvp.reservations SET note = $1 WHERE id = $2 AND ($3::bigint IS NULL OR version = $3) RETURNING *",
+            )
+            .bind(note.clone())
+            .bind(id)
+            .bind(expected_version)
+            .fetch_one(&mut tx)
+            .await?;
+            tx.commit().await?;
+            Ok(rsvp)
+        })
+        .await;
+        match ret {
+            Err(Error::NotFound) => Err(self.resolve_not_found(id, expected_version).await),
+            ret => ret,
+        }
     }
 
     // delete reservation
-    async fn delete(&self, id: ReservationId) -> RsvpResult {
+    async fn delete(
+        &self,
+        id: ReservationId,
+        expected_version: Option<i64>,
+        acted_by: &str,
+    ) -> RsvpResult {
         // let id = Uuid::from_str(&id).map_err(|_| abi::Error::InvalidReservationId(id.clone()))?;
 
         id.validate()?;
+        self.check_acting_on(id, acted_by, DelegationAction::Cancel)
+            .await?;
 
-        let rsvp = sqlx::query_as("DELETE FROM rsvp.reservations WHERE id = $1 RETURNING *")
+        let ret = retry_on_abort(|| async {
+            let mut tx = self.pool.begin().await?;
+            set_acted_by(&mut tx, acted_by).await?;
+            let rsvp = sqlx::query_as(
+                "DELETE FROM rsvp.reservations WHERE id = $1 AND ($2::bigint IS NULL OR version = $2) RETURNING *",
+            )
             .bind(id)
-            .fetch_one(&self.pool)
+            .bind(expected_version)
+            .fetch_one(&mut tx)
             .await?;
-
+            tx.commit().await?;
+            Ok(rsvp)
+        })
+        .await;
+        let rsvp: abi::Reservation = match ret {
+            Err(Error::NotFound) => return Err(self.resolve_not_found(id, expected_version).await),
+            ret => ret?,
+        };
+        self.promote_waitlist(&rsvp).await;
         Ok(rsvp)
     }
 
@@ -104,45 +216,35 @@ impl Rsvp for ReservationManager {
     }
 
     async fn query(&self, query: abi::ReservationQuery) -> ReservationReceiver {
-        let user_id = string_to_option(&query.user_id);
-        let resource_id = string_to_option(&query.resource_id);
-        let start = query.start.map(|ts| convert_time_to_utc(&ts));
-        let end = query.end.map(|ts| convert_time_to_utc(&ts));
-        let status = abi::ReservationStatus::from_i32(query.status)
-            .unwrap_or(abi::ReservationStatus::Pending);
+        self.query_with_timeout(query, None).await
+    }
+
+    async fn query_with_timeout(
+        &self,
+        query: abi::ReservationQuery,
+        timeout: Option<Duration>,
+    ) -> ReservationReceiver {
         let pool = self.pool.clone();
         let (tx, rx) = mpsc::channel(128);
 
+        let limit = match QueryLimit::new(&query, &self.query_config) {
+            Ok(limit) => limit,
+            Err(e) => {
+                // channel is just created, so it must have capacity
+                let _ = tx.try_send(Err(e));
+                return rx;
+            }
+        };
+
+        // the shorter one of grpc deadline and configured statement timeout
+        let config_timeout = Duration::from_millis(self.query_config.statement_timeout_ms);
+        let statement_timeout = timeout.map_or(config_timeout, |t| t.min(config_timeout));
+
         tokio::spawn(async move {
-            let mut rsvps = sqlx::query_as(
-                "SELECT * FROM rsvp.query($1, $2, $3, $4, $5::rsvp.reservation_status, $6)",
-            )
-            .bind(user_id)
-            .bind(resource_id)
-            .bind(start)
-            .bind(end)
-            .bind(status.to_string())
-            .bind(query.desc)
-            .fetch_many(&pool);
-            while let Some(ret) = rsvps.next().await {
-                match ret {
-                    Ok(Either::Left(r)) => {
-                        info!("Query result: {:?}", r);
-                    }
-                    Ok(Either::Right(r)) => {
-                        if tx.send(Ok(r)).await.is_err() {
-                            // rx is dropped, so client disconnected
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        warn!("Query error: {:?}", e);
-                        if tx.send(Err(e.into())).await.is_err() {
-                            // rx is dropped, so client disconnected
-                            break;
-                        }
-                    }
-                }
+            if let Err(e) = stream_query(pool, query, limit, statement_timeout, &tx).await {
+                warn!("Query error: {:?}", e);
+                // if rx is dropped, client disconnected, nothing to do
+                let _ = tx.send(Err(e)).await;
             }
         });
 
@@ -153,27 +255,18 @@ impl Rsvp for ReservationManager {
         &self,
         filter: abi::FilterById,
     ) -> Result<(FilterPager, Vec<abi::Reservation>), Error> {
-        let id = str_to_option(&filter.user_id);
-        let resource_id = str_to_option(&filter.resource_id);
-        let status = abi::ReservationStatus::from_i32(filter.status)
-            .unwrap_or(abi::ReservationStatus::Pending);
+        filter.validate()?;
+
         let page_size = if filter.page_size < 10 || filter.page_size > 100 {
             10
         } else {
             filter.page_size
         };
 
-        let rsvps: Vec<abi::Reservation> = sqlx::query_as(
-            "select * from rsvp.filter($1, $2, $3::rsvp.reservation_status, $4, $5, $6)",
-        )
-        .bind(id)
-        .bind(resource_id)
-        .bind(status.to_string())
-        .bind(filter.cursor)
-        .bind(filter.desc)
-        .bind(page_size)
-        .fetch_all(&self.pool)
-        .await?;
+        let rsvps: Vec<abi::Reservation> = build_filter_sql(&filter, page_size)?
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
 
         // if the first id is current cursor, then we have prev, we start from 1.
         // for example 100 data, cursor is 50, is_desc is false, if the first id is current cursor, which means 1~49 must exist
@@ -221,12 +314,1086 @@ impl Rsvp for ReservationManager {
 
         // ----------------------------------------------------------------------------------------------
     }
+
+    async fn set_policy(&self, policy: abi::ResourcePolicy) -> Result<abi::ResourcePolicy, Error> {
+        policy.validate()?;
+
+        let adjustment =
+            abi::SlotAdjustment::from_i32(policy.adjustment).unwrap_or(abi::SlotAdjustment::Reject);
+
+        // policy and its opening hours are replaced together
+        let mut tx = self.pool.begin().await?;
+        let mut saved: abi::ResourcePolicy = sqlx::query_as(
+            "INSERT INTO rsvp.resource_policies (resource_id, slot_minutes, align_to_slot, adjustment, min_duration_minutes, max_duration_minutes, min_advance_minutes, max_advance_minutes, timezone, holiday_calendars, resource_type)
+            VALUES ($1, $2, $3, $4::rsvp.slot_adjustment, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (resource_id) DO UPDATE SET slot_minutes = EXCLUDED.slot_minutes, align_to_slot = EXCLUDED.align_to_slot, adjustment = EXCLUDED.adjustment,
+                min_duration_minutes = EXCLUDED.min_duration_minutes, max_duration_minutes = EXCLUDED.max_duration_minutes,
+                min_advance_minutes = EXCLUDED.min_advance_minutes, max_advance_minutes = EXCLUDED.max_advance_minutes,
+                timezone = EXCLUDED.timezone, holiday_calendars = EXCLUDED.holiday_calendars, resource_type = EXCLUDED.resource_type
+            RETURNING *",
+        )
+        .bind(policy.resource_id.clone())
+        .bind(policy.slot_minutes)
+        .bind(policy.align_to_slot)
+        .bind(adjustment.to_string())
+        .bind(policy.min_duration_minutes)
+        .bind(policy.max_duration_minutes)
+        .bind(policy.min_advance_minutes)
+        .bind(policy.max_advance_minutes)
+        .bind(policy.timezone.clone())
+        .bind(policy.holiday_calendars.clone())
+        .bind(policy.resource_type.clone())
+        .fetch_one(&mut tx)
+        .await?;
+
+        sqlx::query("DELETE FROM rsvp.opening_hours WHERE resource_id = $1")
+            .bind(policy.resource_id.clone())
+            .execute(&mut tx)
+            .await?;
+        for hours in &policy.opening_hours {
+            sqlx::query(
+                "INSERT INTO rsvp.opening_hours (resource_id, weekday, open_minute, close_minute) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(policy.resource_id.clone())
+            .bind(hours.weekday)
+            .bind(hours.open_minute)
+            .bind(hours.close_minute)
+            .execute(&mut tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        saved.opening_hours = policy.opening_hours;
+        Ok(saved)
+    }
+
+    async fn get_policy(&self, rid: abi::ResourceId) -> Result<abi::ResourcePolicy, Error> {
+        if rid.is_empty() {
+            return Err(Error::InvalidResourceId(rid));
+        }
+
+        let policy: Option<abi::ResourcePolicy> =
+            sqlx::query_as("SELECT * FROM rsvp.resource_policies WHERE resource_id = $1")
+                .bind(rid.clone())
+                .fetch_optional(&self.pool)
+                .await?;
+        let Some(mut policy) = policy else {
+            return Ok(abi::ResourcePolicy::unrestricted(rid));
+        };
+
+        policy.opening_hours = sqlx::query_as(
+            "SELECT * FROM rsvp.opening_hours WHERE resource_id = $1 ORDER BY weekday, open_minute",
+        )
+        .bind(rid)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(policy)
+    }
+
+    async fn set_holidays(
+        &self,
+        calendar: abi::HolidayCalendar,
+    ) -> Result<abi::HolidayCalendar, Error> {
+        calendar.validate()?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM rsvp.holidays WHERE calendar_id = $1")
+            .bind(calendar.id.clone())
+            .execute(&mut tx)
+            .await?;
+        for holiday in &calendar.holidays {
+            sqlx::query("INSERT INTO rsvp.holidays (calendar_id, day, name) VALUES ($1, $2, $3)")
+                .bind(calendar.id.clone())
+                .bind(holiday.day()?)
+                .bind(holiday.name.clone())
+                .execute(&mut tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        self.get_holidays(calendar.id).await
+    }
+
+    async fn get_holidays(&self, calendar_id: String) -> Result<abi::HolidayCalendar, Error> {
+        let holidays =
+            sqlx::query_as("SELECT * FROM rsvp.holidays WHERE calendar_id = $1 ORDER BY day")
+                .bind(calendar_id.clone())
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(abi::HolidayCalendar {
+            id: calendar_id,
+            holidays,
+        })
+    }
+
+    async fn set_resource_group(
+        &self,
+        group: abi::ResourceGroup,
+    ) -> Result<abi::ResourceGroup, Error> {
+        group.validate()?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM rsvp.resource_members WHERE group_id = $1")
+            .bind(group.id.clone())
+            .execute(&mut tx)
+            .await?;
+        for member in &group.member_ids {
+            sqlx::query("INSERT INTO rsvp.resource_members (group_id, member_id) VALUES ($1, $2)")
+                .bind(group.id.clone())
+                .bind(member.clone())
+                .execute(&mut tx)
+                .await?;
+        }
+        // a member can't contain the group again through nested groups
+        let cyclic: Option<String> = sqlx::query_scalar(
+            "SELECT m.member_id FROM rsvp.resource_members m, rsvp.resource_units(m.member_id) u WHERE m.group_id = $1 AND u.resource_id = $1 LIMIT 1",
+        )
+        .bind(group.id.clone())
+        .fetch_optional(&mut tx)
+        .await?;
+        if let Some(member) = cyclic {
+            return Err(Error::InvalidResourceId(member));
+        }
+        // existing reservations of the group must not overlap with the new members
+        sqlx::query("SELECT rsvp.refresh_claims($1)")
+            .bind(group.id.clone())
+            .execute(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        self.get_resource_group(group.id).await
+    }
+
+    async fn get_resource_group(&self, group_id: String) -> Result<abi::ResourceGroup, Error> {
+        let member_ids = sqlx::query_scalar(
+            "SELECT member_id FROM rsvp.resource_members WHERE group_id = $1 ORDER BY member_id",
+        )
+        .bind(group_id.clone())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(abi::ResourceGroup {
+            id: group_id,
+            member_ids,
+        })
+    }
+
+    async fn set_delegation(
+        &self,
+        delegation: abi::Delegation,
+        acted_by: &str,
+    ) -> Result<abi::Delegation, Error> {
+        delegation.validate()?;
+        // only the owner grants, otherwise anyone could grant themselves rights on the owner's reservations
+        if acted_by != delegation.owner_id {
+            return Err(Error::NotDelegated(format!(
+                "{} can't delegate for {}",
+                acted_by, delegation.owner_id
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM rsvp.delegations WHERE owner_id = $1 AND delegate_id = $2")
+            .bind(delegation.owner_id.clone())
+            .bind(delegation.delegate_id.clone())
+            .execute(&mut tx)
+            .await?;
+        for action in delegation.normalized_actions() {
+            sqlx::query(
+                "INSERT INTO rsvp.delegations (owner_id, delegate_id, action) VALUES ($1, $2, $3::rsvp.delegation_action)",
+            )
+            .bind(delegation.owner_id.clone())
+            .bind(delegation.delegate_id.clone())
+            .bind(action.to_string())
+            .execute(&mut tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.get_delegation(delegation.owner_id, delegation.delegate_id)
+            .await
+    }
+
+    async fn get_delegation(
+        &self,
+        owner_id: String,
+        delegate_id: String,
+    ) -> Result<abi::Delegation, Error> {
+        let actions: Vec<String> = sqlx::query_scalar(
+            "SELECT action::text FROM rsvp.delegations WHERE owner_id = $1 AND delegate_id = $2 ORDER BY action",
+        )
+        .bind(owner_id.clone())
+        .bind(delegate_id.clone())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(abi::Delegation {
+            owner_id,
+            delegate_id,
+            actions: actions
+                .iter()
+                .map(|a| DelegationAction::from_db(a) as i32)
+                .collect(),
+        })
+    }
+
+    async fn set_resource(&self, resource: abi::Resource) -> Result<abi::Resource, Error> {
+        resource.validate()?;
+        let parent_id = (!resource.parent_id.is_empty()).then(|| resource.parent_id.clone());
+
+        let mut tx = self.pool.begin().await?;
+        if let Some(parent_id) = &parent_id {
+            // the parent must not be under the resource itself
+            let cyclic: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM rsvp.resource_subtree($1) WHERE resource_id = $2)",
+            )
+            .bind(resource.id.clone())
+            .bind(parent_id.clone())
+            .fetch_one(&mut tx)
+            .await?;
+            if cyclic {
+                return Err(Error::InvalidResourceId(parent_id.clone()));
+            }
+            // parent never set is a root
+            sqlx::query("INSERT INTO rsvp.resources (id) VALUES ($1) ON CONFLICT (id) DO NOTHING")
+                .bind(parent_id.clone())
+                .execute(&mut tx)
+                .await?;
+        }
+        sqlx::query(
+            "INSERT INTO rsvp.resources (id, parent_id) VALUES ($1, $2)
+            ON CONFLICT (id) DO UPDATE SET parent_id = EXCLUDED.parent_id",
+        )
+        .bind(resource.id.clone())
+        .bind(parent_id)
+        .execute(&mut tx)
+        .await?;
+        sqlx::query("DELETE FROM rsvp.resource_attributes WHERE resource_id = $1")
+            .bind(resource.id.clone())
+            .execute(&mut tx)
+            .await?;
+        for (key, value) in &resource.attributes {
+            sqlx::query(
+                "INSERT INTO rsvp.resource_attributes (resource_id, key, value) VALUES ($1, $2, $3)",
+            )
+            .bind(resource.id.clone())
+            .bind(key.clone())
+            .bind(value.clone())
+            .execute(&mut tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.get_resource(resource.id).await
+    }
+
+    async fn get_resource(&self, rid: abi::ResourceId) -> Result<abi::Resource, Error> {
+        if rid.is_empty() {
+            return Err(Error::InvalidResourceId(rid));
+        }
+        let resource = sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1")
+            .bind(rid.clone())
+            .fetch_optional(&self.pool)
+            .await?;
+        let mut resources = vec![resource.unwrap_or_else(|| abi::Resource::root(rid))];
+        self.load_attributes(&mut resources).await?;
+        Ok(resources.remove(0))
+    }
+
+    async fn find_available(
+        &self,
+        request: abi::FindAvailableRequest,
+    ) -> Result<Vec<abi::Resource>, Error> {
+        request.validate()?;
+
+        let mut candidates: Vec<abi::Resource> = build_available_sql(&request)?
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+        self.load_attributes(&mut candidates).await?;
+
+        // free but maybe not bookable, check the policy of each resource as reserve does.
+        // policies and holidays of all candidates are loaded at once
+        let window = abi::Reservation {
+            start: request.start.clone(),
+            end: request.end.clone(),
+            ..Default::default()
+        };
+        let ids: Vec<String> = candidates.iter().map(|r| r.id.clone()).collect();
+        let policies = self.policies_of(&ids).await?;
+        let calendars: Vec<String> = policies
+            .values()
+            .flat_map(|p| p.holiday_calendars.clone())
+            .collect();
+        let holidays = self.holidays_in(&calendars, &window).await?;
+        let now = Utc::now();
+
+        let mut available = vec![];
+        for resource in candidates {
+            let policy = &policies[&resource.id];
+            let mut rsvp = abi::Reservation {
+                resource_id: resource.id.clone(),
+                ..window.clone()
+            };
+            let holidays: Vec<NaiveDate> = holidays
+                .iter()
+                .filter(|(calendar, _)| policy.holiday_calendars.contains(calendar))
+                .map(|(_, day)| *day)
+                .collect();
+            let bookable = policy
+                .apply_slot(&mut rsvp)
+                .and_then(|_| policy.check_duration(&rsvp, now))
+                .and_then(|_| policy.check_opening_hours(&rsvp, &holidays));
+            if bookable.is_ok() {
+                available.push(resource);
+            }
+        }
+
+        request.rank(&mut available);
+        let max_rows = self.query_config.max_rows;
+        let limit = if request.limit > 0 && request.limit <= max_rows {
+            request.limit
+        } else {
+            max_rows
+        };
+        available.truncate(limit as usize);
+        Ok(available)
+    }
+
+    async fn join_waitlist(&self, entry: abi::WaitlistEntry) -> Result<abi::WaitlistEntry, Error> {
+        entry.validate()?;
+        let rsvp = entry.to_reservation();
+        // joining for another user is booking for them once promoted
+        self.check_delegation(&rsvp.user_id, rsvp.booked_by(), DelegationAction::Book)
+            .await?;
+        let expires_at = entry
+            .expires_at
+            .as_ref()
+            .map(convert_time_to_utc)
+            .transpose()?;
+
+        let entry: abi::WaitlistEntry = sqlx::query_as(
+            "INSERT INTO rsvp.waitlist (user_id, resource_id, timespan, note, expires_at, created_by) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+        )
+        .bind(rsvp.user_id.clone())
+        .bind(rsvp.resource_id.clone())
+        .bind(rsvp.get_timestamp()?)
+        .bind(rsvp.note.clone())
+        .bind(expires_at)
+        .bind(rsvp.booked_by())
+        .fetch_one(&self.pool)
+        .await?;
+
+        // nothing to wait for if the range is free already
+        self.promote_waitlist(&rsvp).await;
+        self.get_waitlist_entry(entry.id).await
+    }
+
+    async fn leave_waitlist(&self, id: i64, acted_by: &str) -> Result<abi::WaitlistEntry, Error> {
+        id.validate()?;
+        // same grant as joining for the owner
+        let entry = self.get_waitlist_entry(id).await?;
+        let owner = entry.to_reservation().user_id;
+        self.check_delegation(&owner, acted_by, DelegationAction::Book)
+            .await?;
+        Ok(
+            sqlx::query_as("DELETE FROM rsvp.waitlist WHERE id = $1 RETURNING *")
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?,
+        )
+    }
+
+    async fn get_waitlist_entry(&self, id: i64) -> Result<abi::WaitlistEntry, Error> {
+        id.validate()?;
+        Ok(sqlx::query_as("SELECT * FROM rsvp.waitlist WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn suggest_alternatives(
+        &self,
+        rsvp: abi::Reservation,
+        include_similar: bool,
+    ) -> Result<Vec<abi::Reservation>, Error> {
+        rsvp.validate()?;
+        // open-ended reservation has no length to look for
+        let (Some(start), Some(end)) = (rsvp.start.as_ref(), rsvp.end.as_ref()) else {
+            return Ok(vec![]);
+        };
+        let start = convert_time_to_utc(start)?;
+        let end = convert_time_to_utc(end)?;
+
+        let mut resource_ids = vec![rsvp.resource_id.clone()];
+        if include_similar {
+            resource_ids.extend(self.similar_resources(&rsvp.resource_id).await?);
+        }
+
+        let mut alternatives = vec![];
+        for rid in resource_ids {
+            let mut candidate = rsvp.clone();
+            candidate.resource_id = rid;
+            alternatives.extend(self.free_windows(candidate, start, end).await?);
+        }
+        // nearest first, the requested resource wins a tie
+        alternatives.sort_by_key(|(distance, alt)| {
+            (
+                *distance,
+                alt.resource_id != rsvp.resource_id,
+                alt.start.as_ref().map(|t| t.seconds),
+            )
+        });
+        Ok(alternatives
+            .into_iter()
+            .take(MAX_ALTERNATIVES)
+            .map(|(_, alt)| alt)
+            .collect())
+    }
+
+    async fn claim_idempotency_key(
+        &self,
+        method: &str,
+        key: &str,
+        fingerprint: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        // expired keys are removed lazily, a key left in progress (e.g. the server crashed) is freed when its lease runs out
+        sqlx::query(
+            "DELETE FROM rsvp.idempotency_keys WHERE created_at < now() - make_interval(secs => $1) OR (response IS NULL AND locked_until < now())",
+        )
+        .bind(self.idempotency_config.ttl_seconds as f64)
+        .execute(&self.pool)
+        .await?;
+
+        let claimed = sqlx::query(
+            "INSERT INTO rsvp.idempotency_keys (method, key, fingerprint, locked_until) VALUES ($1, $2, $3, now() + make_interval(secs => $4)) ON CONFLICT DO NOTHING",
+        )
+        .bind(method)
+        .bind(key)
+        .bind(&fingerprint)
+        .bind(self.idempotency_config.lease_seconds as f64)
+        .execute(&self.pool)
+        .await?
+        .rows_affected()
+            == 1;
+        if claimed {
+            return Ok(None);
+        }
+
+        let stored: Option<(Vec<u8>, Option<Vec<u8>>)> = sqlx::query_as(
+            "SELECT fingerprint, response FROM rsvp.idempotency_keys WHERE method = $1 AND key = $2",
+        )
+        .bind(method)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+        match stored {
+            Some((stored, _)) if stored != fingerprint => {
+                Err(Error::IdempotencyKeyReused(key.to_string()))
+            }
+            Some((_, Some(response))) => Ok(Some(response)),
+            // in progress, or released just now by a failed request
+            _ => Err(Error::IdempotencyKeyInProgress(key.to_string())),
+        }
+    }
+
+    async fn save_idempotency_response(
+        &self,
+        method: &str,
+        key: &str,
+        response: Vec<u8>,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE rsvp.idempotency_keys SET response = $3 WHERE method = $1 AND key = $2",
+        )
+        .bind(method)
+        .bind(key)
+        .bind(response)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn renew_idempotency_key(&self, method: &str, key: &str) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE rsvp.idempotency_keys SET locked_until = now() + make_interval(secs => $3) WHERE method = $1 AND key = $2 AND response IS NULL",
+        )
+        .bind(method)
+        .bind(key)
+        .bind(self.idempotency_config.lease_seconds as f64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release_idempotency_key(&self, method: &str, key: &str) -> Result<(), Error> {
+        sqlx::query(
+            "DELETE FROM rsvp.idempotency_keys WHERE method = $1 AND key = $2 AND response IS NULL",
+        )
+        .bind(method)
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
 }
 
 impl ReservationManager {
+    // the owner can always act, anyone else needs a delegation from the owner.
+    // the actor must be named, otherwise anyone could act for the owner by leaving it empty
+    async fn check_delegation(
+        &self,
+        owner: &str,
+        actor: &str,
+        action: DelegationAction,
+    ) -> Result<(), Error> {
+        if actor.is_empty() {
+            return Err(Error::NotDelegated(format!(
+                "acting user is required to {} for {}",
+                action, owner
+            )));
+        }
+        if actor == owner {
+            return Ok(());
+        }
+        let delegated: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM rsvp.delegations WHERE owner_id = $1 AND delegate_id = $2 AND action = $3::rsvp.delegation_action)",
+        )
+        .bind(owner)
+        .bind(actor)
+        .bind(action.to_string())
+        .fetch_one(&self.pool)
+        .await?;
+        if !delegated {
+            return Err(Error::NotDelegated(format!(
+                "{} can't {} for {}",
+                actor, action, owner
+            )));
+        }
+        Ok(())
+    }
+
+    // same as check_delegation, the owner is looked up by the reservation id
+    async fn check_acting_on(
+        &self,
+        id: ReservationId,
+        actor: &str,
+        action: DelegationAction,
+    ) -> Result<(), Error> {
+        let rsvp = self.get(id).await?;
+        self.check_delegation(&rsvp.user_id, actor, action).await
+    }
+
+    // nothing matched the id and version, it's a mismatch if the reservation still exists
+    async fn resolve_not_found(&self, id: ReservationId, expected_version: Option<i64>) -> Error {
+        if expected_version.is_none() {
+            return Error::NotFound;
+        }
+        match self.get(id).await {
+            Ok(current) => check_version(&current, expected_version)
+                .err()
+                .unwrap_or(Error::NotFound),
+            Err(e) => e,
+        }
+    }
+
+    // the database message can't be parsed (e.g. different format, or the conflict is on the user),
+    // find the conflicting row directly. other errors are returned as is.
+    async fn resolve_conflict(
+        &self,
+        rsvp: &abi::Reservation,
+        timespan: PgRange<DateTime<Utc>>,
+        user_exclusion: Option<String>,
+        err: Error,
+    ) -> Error {
+        let ret = match err {
+            Error::ConflictReservation(ReservationConflictInfo::Unparsed(detail)) => self
+                .find_conflict(rsvp, timespan, None, detail)
+                .await
+                .map(Error::ConflictReservation),
+            Error::UserConflictReservation(ReservationConflictInfo::Unparsed(detail)) => self
+                .find_conflict(rsvp, timespan, user_exclusion, detail)
+                .await
+                .map(Error::UserConflictReservation),
+            e => Ok(e),
+        };
+        ret.unwrap_or_else(|e| e)
+    }
+
+    // look up the first existing reservation overlapping with rsvp, keep the raw detail if it's gone already.
+    // with user_exclusion, look for the same user on other resources instead of the same resource.
+    async fn find_conflict(
+        &self,
+        rsvp: &abi::Reservation,
+        timespan: PgRange<DateTime<Utc>>,
+        user_exclusion: Option<String>,
+        detail: String,
+    ) -> Result<ReservationConflictInfo, Error> {
+        // skip rsvp itself when it's rescheduled
+        let query = match user_exclusion {
+            // the claims cover the same resource and the resource groups it belongs to or contains
+            None => sqlx::query_as(
+                "SELECT * FROM rsvp.reservations WHERE id IN (SELECT c.reservation_id FROM rsvp.reservation_claims c, rsvp.resource_units($1) u WHERE c.resource_id = u.resource_id AND c.timespan && $2) AND id <> $3 ORDER BY lower(timespan) LIMIT 1",
+            )
+            .bind(rsvp.resource_id.clone())
+            .bind(timespan)
+            .bind(rsvp.id),
+            Some(exclusion) => sqlx::query_as(
+                "SELECT * FROM rsvp.reservations WHERE user_id = $1 AND user_exclusion = $2 AND timespan && $3 AND id <> $4 ORDER BY lower(timespan) LIMIT 1",
+            )
+            .bind(rsvp.user_id.clone())
+            .bind(exclusion)
+            .bind(timespan)
+            .bind(rsvp.id),
+        };
+        let old: Option<abi::Reservation> = query.fetch_optional(&self.pool).await?;
+
+        Ok(match old {
+            Some(old) => ReservationConflictInfo::Parsed(ReservationConflict {
+                new: rsvp.window()?,
+                old: old.window()?,
+            }),
+            None => ReservationConflictInfo::Unparsed(detail),
+        })
+    }
+
+    // reject or snap the misaligned time, then check duration and advance rules of the resource
+    async fn apply_policy(
+        &self,
+        rsvp: &mut abi::Reservation,
+    ) -> Result<abi::ResourcePolicy, Error> {
+        let policy = self.get_policy(rsvp.resource_id.clone()).await?;
+        policy.apply_slot(rsvp)?;
+        policy.check_duration(rsvp, Utc::now())?;
+
+        let holidays = self.holidays_of(&policy, rsvp).await?;
+        policy.check_opening_hours(rsvp, &holidays)?;
+        Ok(policy)
+    }
+
+    // resources of an exclusive type are tagged with the type, so the database rejects the same user overlapping
+    fn user_exclusion(&self, policy: &abi::ResourcePolicy) -> Option<String> {
+        let types = &self.exclusion_config.per_user_resource_types;
+        (!policy.resource_type.is_empty() && types.contains(&policy.resource_type))
+            .then(|| policy.resource_type.clone())
+    }
+
+    // check the user's quotas before rsvp is inserted (or rescheduled, then rsvp itself is not counted).
+    // reservations of the same user are serialized by an advisory lock, so concurrent requests can't both pass.
+    async fn check_quota(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        rsvp: &abi::Reservation,
+        policy: &abi::ResourcePolicy,
+    ) -> Result<(), Error> {
+        let quota = &self.quota_config;
+        if quota.is_unlimited() {
+            return Ok(());
+        }
+
+        // released when the transaction ends
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(rsvp.user_id.clone())
+            .execute(&mut *tx)
+            .await?;
+
+        if let Some(limit) = quota.max_active_reservations {
+            let used: i64 = sqlx::query_scalar(&format!(
+                "SELECT count(*) FROM rsvp.reservations r WHERE {}",
+                ACTIVE_RESERVATIONS_OF_USER
+            ))
+            .bind(rsvp.user_id.clone())
+            .bind(rsvp.id)
+            .fetch_one(&mut *tx)
+            .await?;
+            check_usage("active_reservations".to_string(), used, 1, limit)?;
+        }
+
+        let type_limit = quota
+            .max_active_per_resource_type
+            .get(&policy.resource_type)
+            .filter(|_| !policy.resource_type.is_empty());
+        if let Some(&limit) = type_limit {
+            let used: i64 = sqlx::query_scalar(&format!(
+                "SELECT count(*) FROM rsvp.reservations r JOIN rsvp.resource_policies p ON p.resource_id = r.resource_id WHERE {} AND p.resource_type = $3",
+                ACTIVE_RESERVATIONS_OF_USER
+            ))
+            .bind(rsvp.user_id.clone())
+            .bind(rsvp.id)
+            .bind(policy.resource_type.clone())
+            .fetch_one(&mut *tx)
+            .await?;
+            check_usage(
+                format!("active_reservations:{}", policy.resource_type),
+                used,
+                1,
+                limit,
+            )?;
+        }
+
+        if let Some(hours) = quota.max_hours_per_week {
+            // every week the reservation overlaps, weeks are in the timezone of the resource
+            let start = convert_time_to_utc(rsvp.start.as_ref().ok_or(Error::InvalidTime)?)?;
+            let end = rsvp.end.as_ref().map(convert_time_to_utc).transpose()?;
+            let weeks = policy.weeks_of(start, end)?;
+            let (week_starts, week_ends): (Vec<_>, Vec<_>) = weeks.iter().copied().unzip();
+            let used: Vec<i64> = sqlx::query_scalar(
+                "SELECT (COALESCE(sum(extract(epoch FROM upper(r.timespan * w.week) - lower(r.timespan * w.week))), 0) / 60)::BIGINT
+                FROM (SELECT i, tstzrange(s, e) AS week FROM unnest($3::timestamptz[], $4::timestamptz[]) WITH ORDINALITY AS t(s, e, i)) w
+                LEFT JOIN rsvp.reservations r ON r.user_id = $1 AND r.id <> $2 AND r.status IN ('pending', 'confirmed') AND r.timespan && w.week
+                GROUP BY w.i ORDER BY w.i",
+            )
+            .bind(rsvp.user_id.clone())
+            .bind(rsvp.id)
+            .bind(week_starts)
+            .bind(week_ends)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            for ((week_start, week_end), used) in weeks.into_iter().zip(used) {
+                // open-ended reservation takes the rest of the week
+                let end = end.map_or(week_end, |end| end.min(week_end));
+                let requested = (end - start.max(week_start)).num_minutes();
+                check_usage(
+                    "minutes_per_week".to_string(),
+                    used,
+                    requested,
+                    hours.saturating_mul(60),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // insert the reservation after the delegation, policy and quota checks. with a waitlist entry,
+    // the entry is promoted in the same transaction, so it's never promoted twice
+    async fn insert_reservation(
+        &self,
+        mut rsvp: abi::Reservation,
+        waitlist_entry: Option<i64>,
+    ) -> RsvpResult {
+        rsvp.validate()?;
+        // an empty created_by is stored as user_id by the creator trigger
+        self.check_delegation(&rsvp.user_id, rsvp.booked_by(), DelegationAction::Book)
+            .await?;
+
+        let policy = self.apply_policy(&mut rsvp).await?;
+
+        let status = abi::ReservationStatus::from_i32(rsvp.status)
+            .unwrap_or(abi::ReservationStatus::Pending);
+
+        let timespan: PgRange<DateTime<Utc>> = rsvp.get_timestamp()?;
+        let user_exclusion = self.user_exclusion(&policy);
+
+        let ret: Result<abi::Reservation, Error> = retry_on_abort(|| async {
+            // quota is checked in the same transaction as the insert
+            let mut tx = self.pool.begin().await?;
+            if let Some(id) = waitlist_entry {
+                claim_waitlist_entry(&mut tx, id).await?;
+            }
+            self.check_quota(&mut tx, &rsvp, &policy).await?;
+            let row: abi::Reservation = sqlx::query_as(
+                "INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status, user_exclusion, created_by) VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status, $6, $7) RETURNING *"
+            )
+            .bind(rsvp.user_id.clone())
+            .bind(rsvp.resource_id.clone())
+            .bind(timespan.clone())
+            .bind(rsvp.note.clone())
+            .bind(status.to_string())
+            .bind(user_exclusion.clone())
+            .bind(rsvp.created_by.clone())
+            .fetch_one(&mut tx)
+            .await?;
+            if let Some(id) = waitlist_entry {
+                sqlx::query("UPDATE rsvp.waitlist SET reservation_id = $1 WHERE id = $2")
+                    .bind(row.id)
+                    .bind(id)
+                    .execute(&mut tx)
+                    .await?;
+            }
+            tx.commit().await?;
+            Ok(row)
+        })
+        .await;
+        match ret {
+            Err(e) => Err(self
+                .resolve_conflict(&rsvp, timespan, user_exclusion, e)
+                .await),
+            ret => ret,
+        }
+    }
+
+    // give the range of a cancelled or moved reservation to the waitlist, first come first served.
+    // the range is already freed, so a failure here is only logged and doesn't fail the caller
+    async fn promote_waitlist(&self, freed: &abi::Reservation) {
+        if let Err(e) = self.try_promote_waitlist(freed).await {
+            warn!("Failed to promote waitlist for {:?}: {:?}", freed, e);
+        }
+    }
+
+    async fn try_promote_waitlist(&self, freed: &abi::Reservation) -> Result<(), Error> {
+        // waiting entries overlapping the range, on the resource or its groups and members
+        let entries: Vec<abi::WaitlistEntry> = sqlx::query_as(
+            "SELECT * FROM rsvp.waitlist w WHERE NOT promoted AND (expires_at IS NULL OR expires_at > now()) AND timespan && $2
+            AND EXISTS (SELECT 1 FROM rsvp.resource_units(w.resource_id) a, rsvp.resource_units($1) b WHERE a.resource_id = b.resource_id)
+            ORDER BY id",
+        )
+        .bind(freed.resource_id.clone())
+        .bind(freed.get_timestamp()?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for entry in entries {
+            // the policy, quota and conflicts are checked as reserve() does, an entry which can't be reserved keeps waiting
+            match self
+                .insert_reservation(entry.to_reservation(), Some(entry.id))
+                .await
+            {
+                Ok(_) => {}
+                Err(Error::DbError(e)) => return Err(Error::DbError(e)),
+                Err(Error::DbUnavailable(e)) => return Err(Error::DbUnavailable(e)),
+                Err(Error::DbAborted(e)) => return Err(Error::DbAborted(e)),
+                Err(_) => continue,
+            }
+        }
+        Ok(())
+    }
+
+    // other resources of the same resource type, none if the type is not set
+    async fn similar_resources(&self, rid: &str) -> Result<Vec<String>, Error> {
+        Ok(sqlx::query_scalar(
+            "SELECT resource_id FROM rsvp.resource_policies WHERE resource_type = (SELECT resource_type FROM rsvp.resource_policies WHERE resource_id = $1) AND resource_type <> '' AND resource_id <> $1 ORDER BY resource_id",
+        )
+        .bind(rid)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    // windows of the same length as [start, end) on the resource of rsvp, nearest to start first.
+    // the window is open, not taken and passes the policy of the resource
+    async fn free_windows(
+        &self,
+        rsvp: abi::Reservation,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<(i64, abi::Reservation)>, Error> {
+        // never suggest the past
+        let from = (start - chrono::Duration::days(SUGGEST_HORIZON_DAYS)).max(Utc::now());
+        let to = end + chrono::Duration::days(SUGGEST_HORIZON_DAYS);
+        let horizon = abi::Reservation {
+            start: Some(convert_to_timestamp(from)),
+            end: Some(convert_to_timestamp(to)),
+            ..Default::default()
+        };
+
+        let policy = self.get_policy(rsvp.resource_id.clone()).await?;
+        let holidays = self.holidays_of(&policy, &horizon).await?;
+        let open = policy.open_intervals(from, to, &holidays)?;
+        let busy = self.busy_intervals(&rsvp.resource_id, from, to).await?;
+        let free = abi::subtract_intervals(&open, &busy);
+
+        let mut windows = vec![];
+        for s in abi::nearest_starts(&free, start, end - start) {
+            // an aligned slot can't start in the middle
+            let s = policy.ceil_to_slot(s)?;
+            let mut alt = rsvp.clone();
+            alt.start = Some(convert_to_timestamp(s));
+            alt.end = Some(convert_to_timestamp(s + (end - start)));
+            // the slot may move the window, it must still be free
+            if policy.apply_slot(&mut alt).is_err()
+                || policy.check_duration(&alt, Utc::now()).is_err()
+            {
+                continue;
+            }
+            let (Some(alt_start), Some(alt_end)) = (alt.start.as_ref(), alt.end.as_ref()) else {
+                continue;
+            };
+            let (alt_start, alt_end) = (
+                convert_time_to_utc(alt_start)?,
+                convert_time_to_utc(alt_end)?,
+            );
+            if free.iter().any(|(a, b)| *a <= alt_start && alt_end <= *b) {
+                windows.push(((alt_start - start).num_seconds().abs(), alt));
+            }
+        }
+        Ok(windows)
+    }
+
+    // taken periods of the resource, including the reservations on its groups and members
+    async fn busy_intervals(
+        &self,
+        rid: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<abi::Interval>, Error> {
+        let spans: Vec<PgRange<DateTime<Utc>>> = sqlx::query_scalar(
+            "SELECT c.timespan FROM rsvp.reservation_claims c, rsvp.resource_units($1) u WHERE c.resource_id = u.resource_id AND c.timespan && $2 AND NOT isempty(c.timespan) ORDER BY lower(c.timespan)",
+        )
+        .bind(rid)
+        .bind(PgRange {
+            start: Bound::Included(from),
+            end: Bound::Excluded(to),
+        })
+        .fetch_all(&self.pool)
+        .await?;
+
+        // unbounded sides are cut at the horizon
+        let bound = |b: Bound<DateTime<Utc>>, default: DateTime<Utc>| match b {
+            Bound::Included(t) | Bound::Excluded(t) => t,
+            Bound::Unbounded => default,
+        };
+        Ok(spans
+            .into_iter()
+            .map(|span| (bound(span.start, from), bound(span.end, to)))
+            .collect())
+    }
+
+    // fill the attributes of the resources in one query
+    async fn load_attributes(&self, resources: &mut [abi::Resource]) -> Result<(), Error> {
+        let ids: Vec<String> = resources.iter().map(|r| r.id.clone()).collect();
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT resource_id, key, value FROM rsvp.resource_attributes WHERE resource_id = ANY($1)",
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+        for (rid, key, value) in rows {
+            if let Some(resource) = resources.iter_mut().find(|r| r.id == rid) {
+                resource.attributes.insert(key, value);
+            }
+        }
+        Ok(())
+    }
+
+    // holidays around the reservation in the calendars of the policy, one more day at both ends for the timezone
+    async fn holidays_of(
+        &self,
+        policy: &abi::ResourcePolicy,
+        rsvp: &abi::Reservation,
+    ) -> Result<Vec<NaiveDate>, Error> {
+        let mut days: Vec<NaiveDate> = self
+            .holidays_in(&policy.holiday_calendars, rsvp)
+            .await?
+            .into_iter()
+            .map(|(_, day)| day)
+            .collect();
+        days.sort();
+        days.dedup();
+        Ok(days)
+    }
+
+    // (calendar, day) of the holidays around the reservation in any of the calendars, same range as holidays_of
+    async fn holidays_in(
+        &self,
+        calendars: &[String],
+        rsvp: &abi::Reservation,
+    ) -> Result<Vec<(String, NaiveDate)>, Error> {
+        let (Some(start), Some(end)) = (rsvp.start.as_ref(), rsvp.end.as_ref()) else {
+            return Ok(vec![]);
+        };
+        if calendars.is_empty() {
+            return Ok(vec![]);
+        }
+        let start = (convert_time_to_utc(start)? - chrono::Duration::days(1)).date_naive();
+        let end = (convert_time_to_utc(end)? + chrono::Duration::days(1)).date_naive();
+
+        Ok(sqlx::query_as(
+            "SELECT calendar_id, day FROM rsvp.holidays WHERE calendar_id = ANY($1) AND day BETWEEN $2 AND $3",
+        )
+        .bind(calendars)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    // policies of the resources by id, unrestricted for resources without one. same as get_policy for each
+    async fn policies_of(
+        &self,
+        ids: &[String],
+    ) -> Result<HashMap<String, abi::ResourcePolicy>, Error> {
+        let mut policies: HashMap<String, abi::ResourcePolicy> = ids
+            .iter()
+            .map(|id| (id.clone(), abi::ResourcePolicy::unrestricted(id.clone())))
+            .collect();
+        let rows: Vec<abi::ResourcePolicy> =
+            sqlx::query_as("SELECT * FROM rsvp.resource_policies WHERE resource_id = ANY($1)")
+                .bind(ids)
+                .fetch_all(&self.pool)
+                .await?;
+        for policy in rows {
+            policies.insert(policy.resource_id.clone(), policy);
+        }
+
+        let hours = sqlx::query(
+            "SELECT * FROM rsvp.opening_hours WHERE resource_id = ANY($1) ORDER BY weekday, open_minute",
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+        for row in hours {
+            let rid: String = row.try_get("resource_id")?;
+            if let Some(policy) = policies.get_mut(&rid) {
+                policy
+                    .opening_hours
+                    .push(abi::OpeningHours::from_row(&row)?);
+            }
+        }
+        Ok(policies)
+    }
+
     // 創建一個新的 ReservationManager 實例，並將傳入的 pool 綁定到這個實例上。
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            query_config: QueryConfig::default(),
+            quota_config: QuotaConfig::default(),
+            exclusion_config: ExclusionConfig::default(),
+            idempotency_config: IdempotencyConfig::default(),
+        }
+    }
+
+    // 替換預設的 query 限制 (最大筆數、最大時間區間、statement timeout)
+    pub fn with_query_config(mut self, query_config: QueryConfig) -> Self {
+        self.query_config = query_config;
+        self
+    }
+
+    // 設定每個使用者的預約額度，預設沒有限制
+    pub fn with_quota_config(mut self, quota_config: QuotaConfig) -> Self {
+        self.quota_config = quota_config;
+        self
+    }
+
+    // 同一個使用者不能同時預約這些類型的多個資源，預設不限制
+    pub fn with_exclusion_config(mut self, exclusion_config: ExclusionConfig) -> Self {
+        self.exclusion_config = exclusion_config;
+        self
+    }
+
+    // idempotency key 對應的回應保留多久 (預設一天)，以及處理中的 key 被保留多久
+    pub fn with_idempotency_config(mut self, idempotency_config: IdempotencyConfig) -> Self {
+        self.idempotency_config = idempotency_config;
+        self
+    }
+
+    // run the request of a claimed idempotency key, the claim is renewed before its lease runs out,
+    // so a slow request is never run a second time with the same key
+    pub async fn while_claimed<F: Future>(&self, method: &str, key: &str, request: F) -> F::Output {
+        let lease = Duration::from_secs(self.idempotency_config.lease_seconds.max(1) as u64);
+        let mut renew = tokio::time::interval(lease / 3);
+        // the first tick is at once, the key is just claimed
+        renew.tick().await;
+        tokio::pin!(request);
+        loop {
+            tokio::select! {
+                output = &mut request => return output,
+                _ = renew.tick() => {
+                    if let Err(e) = self.renew_idempotency_key(method, key).await {
+                        warn!("failed to renew idempotency key {}: {:?}", key, e);
+                    }
+                }
+            }
+        }
     }
 
     // 從 config 裡面取得資料庫的連線資訊，並且建立一個新的 ReservationManager 實例。
@@ -240,18 +1407,426 @@ impl ReservationManager {
     }
 }
 
-fn str_to_option(s: &str) -> Option<&str> {
-    if s.is_empty() {
-        None
+// pending or confirmed reservations of user $1 which are not over yet, except reservation $2
+const ACTIVE_RESERVATIONS_OF_USER: &str = "r.user_id = $1 AND r.id <> $2 AND r.status IN ('pending', 'confirmed') AND (upper_inf(r.timespan) OR upper(r.timespan) > now())";
+
+fn check_usage(quota: String, used: i64, requested: i64, limit: i64) -> Result<(), Error> {
+    if used.saturating_add(requested) > limit {
+        return Err(Error::QuotaExceeded(QuotaUsage { quota, used, limit }));
+    }
+    Ok(())
+}
+
+// mark the entry promoted before its reservation is inserted, the row lock makes a concurrent promotion wait
+// and then find it promoted already. the trigger records the insert as 'promote'
+async fn claim_waitlist_entry(tx: &mut Transaction<'_, Postgres>, id: i64) -> Result<(), Error> {
+    let claimed =
+        sqlx::query("UPDATE rsvp.waitlist SET promoted = true WHERE id = $1 AND NOT promoted")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+    if claimed == 0 {
+        return Err(Error::NotFound);
+    }
+    sqlx::query("SELECT set_config('rsvp.change_op', 'promote', true)")
+        .execute(tx)
+        .await?;
+    Ok(())
+}
+
+// recorded as acted_by of reservation_changes by the trigger, only for this transaction
+async fn set_acted_by(tx: &mut Transaction<'_, Postgres>, acted_by: &str) -> Result<(), Error> {
+    sqlx::query("SELECT set_config('rsvp.acted_by', $1, true)")
+        .bind(acted_by)
+        .execute(tx)
+        .await?;
+    Ok(())
+}
+
+// no check if the client didn't send a version
+fn check_version(current: &abi::Reservation, expected_version: Option<i64>) -> Result<(), Error> {
+    match expected_version {
+        Some(version) if version != current.version => {
+            Err(Error::VersionMismatch(Box::new(current.clone())))
+        }
+        _ => Ok(()),
+    }
+}
+
+// how far before and after the requested time to look for alternatives, and how many to return
+const SUGGEST_HORIZON_DAYS: i64 = 7;
+const MAX_ALTERNATIVES: usize = 3;
+
+const MAX_RETRIES: u32 = 3;
+const RETRY_BACKOFF: Duration = Duration::from_millis(10);
+
+// serialization failure and deadlock are rolled back by postgres, so it's safe to run the statement again.
+// other errors (include the transient DbUnavailable) are returned to the client at once.
+pub(crate) async fn retry_on_abort<T, F, Fut>(mut f: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Err(Error::DbAborted(e)) if attempt < MAX_RETRIES => {
+                attempt += 1;
+                warn!(
+                    "Transaction aborted, retry {}/{}: {:?}",
+                    attempt, MAX_RETRIES, e
+                );
+                tokio::time::sleep(RETRY_BACKOFF * 2u32.pow(attempt)).await;
+            }
+            ret => return ret,
+        }
+    }
+}
+
+// how many rows a query may return, and whether hitting it means the result is truncated by the server
+struct QueryLimit {
+    rows: i64,
+    truncated_by_server: bool,
+}
+
+impl QueryLimit {
+    fn new(query: &abi::ReservationQuery, config: &QueryConfig) -> Result<Self, Error> {
+        query.validate()?;
+
+        if let Some(max_days) = config.max_window_days {
+            let window = match (query.start.as_ref(), query.end.as_ref()) {
+                (Some(start), Some(end)) => {
+                    Some(convert_time_to_utc(end)? - convert_time_to_utc(start)?)
+                }
+                _ => None,
+            };
+            // an open ended window is always too large
+            if window.is_none_or(|w| w.num_seconds() > max_days.saturating_mul(24 * 60 * 60)) {
+                return Err(Error::QueryWindowTooLarge(max_days));
+            }
+        }
+
+        Ok(if query.limit > 0 && query.limit <= config.max_rows {
+            Self {
+                rows: query.limit,
+                truncated_by_server: false,
+            }
+        } else {
+            Self {
+                rows: config.max_rows,
+                truncated_by_server: true,
+            }
+        })
+    }
+}
+
+// run the query in a transaction so that statement_timeout only applies to it, then send rows one by one
+async fn stream_query(
+    pool: PgPool,
+    query: abi::ReservationQuery,
+    limit: QueryLimit,
+    statement_timeout: Duration,
+    tx: &mpsc::Sender<RsvpResult>,
+) -> Result<(), Error> {
+    // statement_timeout is in ms and 0 disables it, so a deadline with less than 1ms left is already reached
+    if statement_timeout < Duration::from_millis(1) {
+        return Err(Error::QueryTimeout);
+    }
+
+    let mut transaction = pool.begin().await?;
+    sqlx::query("SELECT set_config('statement_timeout', $1, true)")
+        .bind(statement_timeout.as_millis().to_string())
+        .execute(&mut transaction)
+        .await?;
+
+    // fetch one more row to know if there are more rows than the limit
+    let mut builder = build_query_sql(&query, limit.rows.saturating_add(1))?;
+    let mut rsvps = builder
+        .build_query_as::<abi::Reservation>()
+        .fetch_many(&mut transaction);
+
+    let mut sent = 0;
+    while let Some(ret) = rsvps.next().await {
+        match ret? {
+            Either::Left(r) => {
+                info!("Query result: {:?}", r);
+            }
+            Either::Right(r) => {
+                if sent == limit.rows {
+                    if limit.truncated_by_server {
+                        let _ = tx.send(Err(Error::QueryTruncated(limit.rows))).await;
+                    }
+                    break;
+                }
+                if tx.send(Ok(r)).await.is_err() {
+                    // rx is dropped, so client disconnected
+                    break;
+                }
+                sent += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// build the sql for query(), every value goes through push_bind so the statement can be prepared and cached
+fn build_query_sql(
+    query: &abi::ReservationQuery,
+    limit: i64,
+) -> Result<QueryBuilder<'static, Postgres>, Error> {
+    let status =
+        abi::ReservationStatus::from_i32(query.status).unwrap_or(abi::ReservationStatus::Pending);
+    let match_mode =
+        abi::TimeMatchMode::from_i32(query.match_mode).unwrap_or(abi::TimeMatchMode::Contained);
+    let during = query.get_timespan()?;
+
+    let mut builder = QueryBuilder::new("SELECT * FROM rsvp.reservations WHERE ");
+    match match_mode {
+        abi::TimeMatchMode::Overlaps => {
+            builder.push("timespan && ").push_bind(during);
+        }
+        abi::TimeMatchMode::Contains => {
+            builder.push("timespan @> ").push_bind(during);
+        }
+        abi::TimeMatchMode::Contained | abi::TimeMatchMode::Unknown => {
+            builder.push_bind(during).push(" @> timespan");
+        }
+    }
+    push_common_conditions(
+        &mut builder,
+        status,
+        merge_ids(&query.user_id, &query.user_ids),
+        merge_ids(&query.resource_id, &query.resource_ids),
+        &query.location_id,
+    );
+    push_audit_conditions(
+        &mut builder,
+        &query.created_by,
+        query.created_between.as_ref(),
+        query.updated_between.as_ref(),
+    )?;
+    let column = sort_column(query.sort_by, abi::ReservationSortKey::Start);
+    let direction = sort_direction(query.desc);
+    builder
+        .push(format!(
+            " ORDER BY {} {}, id {} LIMIT ",
+            column, direction, direction
+        ))
+        .push_bind(limit);
+
+    Ok(builder)
+}
+
+// build the sql for find_available(), resources which are free in the window and match all predicates
+fn build_available_sql(
+    request: &abi::FindAvailableRequest,
+) -> Result<QueryBuilder<'static, Postgres>, Error> {
+    let during =
+        convert_timestamp_into_window_pgrange(request.start.as_ref(), request.end.as_ref())?;
+
+    // busy if any unit of the resource (itself, or a member if it's a group) is claimed, uses the gist index of the claims
+    let mut builder = QueryBuilder::new(
+        "SELECT * FROM rsvp.resources r WHERE NOT EXISTS (SELECT 1 FROM rsvp.reservation_claims c, rsvp.resource_units(r.id) u WHERE c.resource_id = u.resource_id AND c.timespan && ",
+    );
+    builder.push_bind(during).push(")");
+    if !request.location_id.is_empty() {
+        builder
+            .push(" AND r.id IN (SELECT resource_id FROM rsvp.resource_subtree(")
+            .push_bind(request.location_id.clone())
+            .push("))");
+    }
+
+    for predicate in &request.predicates {
+        // a resource without the attribute is not equal to anything
+        let exists = if predicate.operator() == abi::AttributeOp::Ne {
+            " AND NOT EXISTS"
+        } else {
+            " AND EXISTS"
+        };
+        builder
+            .push(exists)
+            .push(" (SELECT 1 FROM rsvp.resource_attributes a WHERE a.resource_id = r.id AND a.key = ")
+            .push_bind(predicate.key.clone());
+        match predicate.operator() {
+            abi::AttributeOp::Exists => {}
+            abi::AttributeOp::Eq | abi::AttributeOp::Ne | abi::AttributeOp::Unknown => {
+                builder
+                    .push(" AND a.value = ")
+                    .push_bind(predicate.value.clone());
+            }
+            op => {
+                let operator = match op {
+                    abi::AttributeOp::Gt => ">",
+                    abi::AttributeOp::Gte => ">=",
+                    abi::AttributeOp::Lt => "<",
+                    _ => "<=",
+                };
+                // non-numeric attribute is NULL, so it never matches
+                builder
+                    .push(" AND CASE WHEN a.value ~ '^-?[0-9]+(\\.[0-9]+)?$' THEN a.value::float8 END ")
+                    .push(operator)
+                    .push(" ")
+                    .push_bind(predicate.number().unwrap_or_default());
+            }
+        }
+        builder.push(")");
+    }
+    builder.push(" ORDER BY r.id");
+
+    Ok(builder)
+}
+
+// build the sql for keyset_query(), fetch one more row than page_size to know if there is a next page
+fn build_filter_sql(
+    filter: &abi::FilterById,
+    page_size: i64,
+) -> Result<QueryBuilder<'static, Postgres>, Error> {
+    let status =
+        abi::ReservationStatus::from_i32(filter.status).unwrap_or(abi::ReservationStatus::Pending);
+    // if the cursor is negative, start from the first (or the last if desc) reservation
+    let cursor = match (filter.cursor < 0, filter.desc) {
+        (true, true) => i64::MAX,
+        (true, false) => 0,
+        (false, _) => filter.cursor,
+    };
+
+    let column = sort_column(filter.sort_by, abi::ReservationSortKey::Id);
+    let direction = sort_direction(filter.desc);
+    let compare = if filter.desc { " <= " } else { " >= " };
+
+    let mut builder = QueryBuilder::new("SELECT * FROM rsvp.reservations WHERE ");
+    if column == "id" {
+        builder.push("id").push(compare).push_bind(cursor);
+    } else if filter.cursor < 0 {
+        builder.push("true");
     } else {
-        Some(s)
+        // continue from the cursor row, id breaks the tie of the same sort value
+        builder
+            .push(format!(
+                "({}, id){}(SELECT {}, id FROM rsvp.reservations WHERE id = ",
+                column, compare, column
+            ))
+            .push_bind(cursor)
+            .push(")");
+    }
+    push_common_conditions(
+        &mut builder,
+        status,
+        merge_ids(&filter.user_id, &filter.user_ids),
+        merge_ids(&filter.resource_id, &filter.resource_ids),
+        &filter.location_id,
+    );
+    if filter.start.is_some() || filter.end.is_some() {
+        let during =
+            convert_timestamp_into_window_pgrange(filter.start.as_ref(), filter.end.as_ref())?;
+        builder.push(" AND ").push_bind(during).push(" @> timespan");
+    }
+    push_audit_conditions(
+        &mut builder,
+        &filter.created_by,
+        filter.created_between.as_ref(),
+        filter.updated_between.as_ref(),
+    )?;
+    if column == "id" {
+        builder.push(format!(" ORDER BY id {} LIMIT ", direction));
+    } else {
+        builder.push(format!(
+            " ORDER BY {} {}, id {} LIMIT ",
+            column, direction, direction
+        ));
+    }
+    builder.push_bind(page_size + 1);
+
+    Ok(builder)
+}
+
+// status, user ids, resource ids and location conditions shared by query() and keyset_query(),
+// empty ids means no filter
+fn push_common_conditions(
+    builder: &mut QueryBuilder<'static, Postgres>,
+    status: abi::ReservationStatus,
+    user_ids: Vec<String>,
+    resource_ids: Vec<String>,
+    location_id: &str,
+) {
+    builder
+        .push(" AND status = ")
+        .push_bind(status.to_string())
+        .push("::rsvp.reservation_status");
+    if !user_ids.is_empty() {
+        builder
+            .push(" AND user_id = ANY(")
+            .push_bind(user_ids)
+            .push(")");
+    }
+    if !resource_ids.is_empty() {
+        builder
+            .push(" AND resource_id = ANY(")
+            .push_bind(resource_ids)
+            .push(")");
+    }
+    if !location_id.is_empty() {
+        builder
+            .push(" AND resource_id IN (SELECT resource_id FROM rsvp.resource_subtree(")
+            .push_bind(location_id.to_string())
+            .push("))");
+    }
+}
+
+// creator and created/updated windows shared by query() and keyset_query(), not set means no filter
+fn push_audit_conditions(
+    builder: &mut QueryBuilder<'static, Postgres>,
+    created_by: &str,
+    created_between: Option<&abi::TimeWindow>,
+    updated_between: Option<&abi::TimeWindow>,
+) -> Result<(), Error> {
+    if !created_by.is_empty() {
+        builder
+            .push(" AND created_by = ")
+            .push_bind(created_by.to_string());
+    }
+    if let Some(window) = created_between {
+        builder
+            .push(" AND created_at <@ ")
+            .push_bind(window.get_range()?);
+    }
+    if let Some(window) = updated_between {
+        builder
+            .push(" AND updated_at <@ ")
+            .push_bind(window.get_range()?);
+    }
+    Ok(())
+}
+
+// the single value field is kept for backward compatibility, merge it into the repeated one
+fn merge_ids(id: &str, ids: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = ids.iter().filter(|id| !id.is_empty()).cloned().collect();
+    if !id.is_empty() && !merged.iter().any(|v| v == id) {
+        merged.push(id.to_string());
+    }
+    merged
+}
+
+// only fixed column names go into the sql
+fn sort_column(sort_by: i32, default: abi::ReservationSortKey) -> &'static str {
+    let key = match abi::ReservationSortKey::from_i32(sort_by) {
+        Some(abi::ReservationSortKey::Unknown) | None => default,
+        Some(key) => key,
+    };
+    match key {
+        abi::ReservationSortKey::Start => "lower(timespan)",
+        abi::ReservationSortKey::CreatedAt => "created_at",
+        abi::ReservationSortKey::UpdatedAt => "updated_at",
+        abi::ReservationSortKey::Id | abi::ReservationSortKey::Unknown => "id",
     }
 }
 
-fn string_to_option(s: &str) -> Option<String> {
-    if s.is_empty() {
-        None
+fn sort_direction(desc: bool) -> &'static str {
+    if desc {
+        "DESC"
     } else {
-        Some(s.into())
+        "ASC"
     }
 }
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::ResourcePolicy;
+    use sqlx::PgPool;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn alternatives_should_be_the_nearest_free_windows() {
+        let manager = make_manager(migrated_pool.clone()).await;
+
+        let rsvp = make_rsvp("yangid", "room-1", &at("10:30"), &at("11:30"));
+        assert!(manager.reserve(rsvp.clone()).await.is_err());
+
+        // before 10:00 and after 12:00 are equally near, the earlier one first
+        let alternatives = manager
+            .suggest_alternatives(rsvp.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            alternatives,
+            vec![
+                make_rsvp("yangid", "room-1", &at("09:00"), &at("10:00")),
+                make_rsvp("yangid", "room-1", &at("12:00"), &at("13:00")),
+            ]
+        );
+
+        // room-2 is free at the requested time, room-3 is another type
+        let alternatives = manager.suggest_alternatives(rsvp, true).await.unwrap();
+        assert_eq!(
+            alternatives,
+            vec![
+                make_rsvp("yangid", "room-2", &at("10:30"), &at("11:30")),
+                make_rsvp("yangid", "room-1", &at("09:00"), &at("10:00")),
+                make_rsvp("yangid", "room-1", &at("12:00"), &at("13:00")),
+            ]
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn alternatives_should_follow_the_policy() {
+        let manager = make_manager(migrated_pool.clone()).await;
+        manager
+            .reserve(make_rsvp("yangid", "room-1", &at("12:00"), &at("12:30")))
+            .await
+            .unwrap();
+        // whole hours only, so 12:30 - 13:30 is never suggested
+        manager
+            .set_policy(ResourcePolicy {
+                resource_id: "room-1".to_string(),
+                resource_type: "meeting-room".to_string(),
+                slot_minutes: 60,
+                align_to_slot: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let alternatives = manager
+            .suggest_alternatives(
+                make_rsvp("yangid", "room-1", &at("11:00"), &at("12:00")),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            alternatives,
+            vec![
+                make_rsvp("yangid", "room-1", &at("09:00"), &at("10:00")),
+                make_rsvp("yangid", "room-1", &at("13:00"), &at("14:00")),
+            ]
+        );
+
+        // open-ended reservation has no length
+        let rsvp =
+            abi::Reservation::new_open_ended("yangid", "room-1", at("10:00").parse().unwrap(), "");
+        assert!(manager
+            .suggest_alternatives(rsvp, true)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    // room-1 and room-2 are meeting rooms, room-3 is a desk. room-1 is taken 10:00 - 12:00
+    async fn make_manager(pool: PgPool) -> ReservationManager {
+        let manager = ReservationManager::new(pool);
+        set_resource_types(
+            &manager,
+            &[
+                ("room-1", "meeting-room"),
+                ("room-2", "meeting-room"),
+                ("room-3", "desk"),
+            ],
+        )
+        .await;
+        for (start, end) in [("10:00", "11:00"), ("11:00", "12:00")] {
+            manager
+                .reserve(make_rsvp("yangid", "room-1", &at(start), &at(end)))
+                .await
+                .unwrap();
+        }
+        manager
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{
+        Delegation, DelegationAction, FilterByIdBuilder, Reservation, ReservationQueryBuilder,
+        ReservationSortKey, TimeWindow,
+    };
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reservation_should_record_when_and_by_whom() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        grant_booking(&manager).await;
+        let on_behalf = manager
+            .reserve(Reservation {
+                created_by: "assistantid".to_string(),
+                ..make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(on_behalf.user_id, "bossid");
+        assert_eq!(on_behalf.created_by, "assistantid");
+        assert!(on_behalf.created_at.is_some());
+        assert_eq!(on_behalf.updated_at, on_behalf.created_at);
+
+        // booked by the user themselves
+        let own = manager
+            .reserve(make_rsvp("bossid", "room-1", &at("11:00"), &at("12:00")))
+            .await
+            .unwrap();
+        assert_eq!(own.created_by, "bossid");
+
+        let updated = manager
+            .update_note(
+                on_behalf.id,
+                "moved to the big room".to_string(),
+                None,
+                "assistantid",
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.created_at, on_behalf.created_at);
+        assert_eq!(updated.created_by, "assistantid");
+        let time =
+            |r: &Reservation| abi::convert_time_to_utc(r.updated_at.as_ref().unwrap()).unwrap();
+        assert!(time(&updated) > time(&on_behalf));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_and_filter_should_sort_and_filter_by_audit_fields() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        grant_booking(&manager).await;
+        let mut made = vec![];
+        for (start, end) in [("10:00", "11:00"), ("11:00", "12:00"), ("12:00", "13:00")] {
+            made.push(
+                manager
+                    .reserve(Reservation {
+                        created_by: "assistantid".to_string(),
+                        ..make_rsvp("bossid", "room-1", &at(start), &at(end))
+                    })
+                    .await
+                    .unwrap(),
+            );
+        }
+        manager
+            .reserve(make_rsvp("bossid", "room-1", &at("13:00"), &at("14:00")))
+            .await
+            .unwrap();
+        // the first one is changed last
+        let first = manager
+            .update_note(made[0].id, "changed".to_string(), None, "bossid")
+            .await
+            .unwrap();
+        let expected = vec![made[1].clone(), made[2].clone(), first.clone()];
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("bossid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .created_by("assistantid")
+            .sort_by(ReservationSortKey::UpdatedAt as i32)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        let mut rsvps = vec![];
+        while let Some(Ok(rsvp)) = rx.recv().await {
+            rsvps.push(rsvp);
+        }
+        assert_eq!(rsvps, expected);
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("bossid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .updated_between(TimeWindow {
+                start: first.updated_at.clone(),
+                end: None,
+            })
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, Some(Ok(first.clone())));
+        assert_eq!(rx.recv().await, None);
+
+        // the cursor is still an id, the page goes on after that row in the sort order
+        let filter = FilterByIdBuilder::default()
+            .user_id("bossid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .created_by("assistantid")
+            .sort_by(ReservationSortKey::UpdatedAt as i32)
+            .cursor(made[1].id)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(rsvps, expected[1..].to_vec());
+
+        let filter = FilterByIdBuilder::default()
+            .user_id("bossid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .created_by("assistantid")
+            .sort_by(ReservationSortKey::UpdatedAt as i32)
+            .desc(true)
+            .cursor(-1)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(
+            rsvps,
+            expected.iter().rev().cloned().collect::<Vec<Reservation>>()
+        );
+    }
+
+    async fn grant_booking(manager: &ReservationManager) {
+        manager
+            .set_delegation(
+                Delegation {
+                    owner_id: "bossid".to_string(),
+                    delegate_id: "assistantid".to_string(),
+                    actions: vec![DelegationAction::Book as i32],
+                },
+                "bossid",
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use crate::{ReservationManager, Rsvp};
+    use abi::{
+        AttributeOp, AttributePredicate, FindAvailableRequest, OpeningHours, Resource,
+        ResourcePolicy,
+    };
+    use sqlx::PgPool;
+
+    // 2030-01-08 is Tuesday
+    const START: &str = "2030-01-08T14:00:00+0000";
+    const END: &str = "2030-01-08T15:00:00+0000";
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn find_available_should_return_free_matching_resources_by_fit() {
+        let manager = make_manager(migrated_pool.clone()).await;
+        let predicates = vec![
+            predicate("capacity", AttributeOp::Gte, "8"),
+            predicate("projector", AttributeOp::Eq, "true"),
+        ];
+
+        // room-a is booked, room-c has no projector, room-d is closed on tuesday
+        let found = manager
+            .find_available(make_request(predicates.clone(), ""))
+            .await
+            .unwrap();
+        assert_eq!(ids(&found), vec!["room-e", "room-b"]);
+        assert_eq!(found[0].attributes["capacity"], "10");
+
+        let found = manager
+            .find_available(make_request(predicates.clone(), "building-1"))
+            .await
+            .unwrap();
+        assert_eq!(ids(&found), vec!["room-b"]);
+
+        let mut request = make_request(predicates, "");
+        request.limit = 1;
+        let found = manager.find_available(request).await.unwrap();
+        assert_eq!(ids(&found), vec!["room-e"]);
+
+        let found = manager
+            .find_available(make_request(
+                vec![predicate("projector", AttributeOp::Ne, "true")],
+                "building-1",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(ids(&found), vec!["building-1", "room-c"]);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn find_available_should_follow_booking_rules() {
+        let manager = make_manager(migrated_pool.clone()).await;
+        // room-b is for 30 minutes at most, room-e is booked one day ahead at most
+        for policy in [
+            ResourcePolicy {
+                resource_id: "room-b".to_string(),
+                max_duration_minutes: 30,
+                ..Default::default()
+            },
+            ResourcePolicy {
+                resource_id: "room-e".to_string(),
+                max_advance_minutes: 24 * 60,
+                ..Default::default()
+            },
+        ] {
+            manager.set_policy(policy).await.unwrap();
+        }
+
+        let found = manager
+            .find_available(make_request(
+                vec![predicate("projector", AttributeOp::Eq, "true")],
+                "",
+            ))
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+        // reserve agrees
+        for rid in ["room-b", "room-e"] {
+            let rsvp = abi::Reservation::new_pending(
+                "yangid",
+                rid,
+                START.parse().unwrap(),
+                END.parse().unwrap(),
+                "",
+            );
+            assert!(manager.reserve(rsvp).await.is_err());
+        }
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn find_available_should_reject_invalid_request() {
+        let manager = make_manager(migrated_pool.clone()).await;
+
+        let err = manager
+            .find_available(make_request(
+                vec![predicate("capacity", AttributeOp::Gte, "many")],
+                "",
+            ))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::InvalidPredicate(_)));
+
+        let mut request = make_request(vec![], "");
+        request.end = None;
+        let err = manager.find_available(request).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
+    }
+
+    // building-1 > room-a, room-b, room-c, room-d. building-2 > room-e
+    async fn make_manager(pool: PgPool) -> ReservationManager {
+        let manager = ReservationManager::new(pool);
+        for (id, parent_id, capacity, projector) in [
+            ("room-a", "building-1", "8", true),
+            ("room-b", "building-1", "20", true),
+            ("room-c", "building-1", "8", false),
+            ("room-d", "building-1", "12", true),
+            ("room-e", "building-2", "10", true),
+        ] {
+            let mut attributes = vec![("capacity".to_string(), capacity.to_string())];
+            if projector {
+                attributes.push(("projector".to_string(), "true".to_string()));
+            }
+            manager
+                .set_resource(Resource {
+                    id: id.to_string(),
+                    parent_id: parent_id.to_string(),
+                    attributes: attributes.into_iter().collect(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let rsvp = abi::Reservation::new_pending(
+            "yangid",
+            "room-a",
+            START.parse().unwrap(),
+            END.parse().unwrap(),
+            "",
+        );
+        manager.reserve(rsvp).await.unwrap();
+        // monday only
+        manager
+            .set_policy(ResourcePolicy {
+                resource_id: "room-d".to_string(),
+                opening_hours: vec![OpeningHours {
+                    weekday: 1,
+                    open_minute: 0,
+                    close_minute: 24 * 60,
+                }],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        manager
+    }
+
+    fn make_request(
+        predicates: Vec<AttributePredicate>,
+        location_id: &str,
+    ) -> FindAvailableRequest {
+        FindAvailableRequest {
+            start: Some(START.parse().unwrap()),
+            end: Some(END.parse().unwrap()),
+            predicates,
+            location_id: location_id.to_string(),
+            limit: 0,
+        }
+    }
+
+    fn predicate(key: &str, op: AttributeOp, value: &str) -> AttributePredicate {
+        AttributePredicate {
+            key: key.to_string(),
+            op: op as i32,
+            value: value.to_string(),
+        }
+    }
+
+    fn ids(resources: &[Resource]) -> Vec<&str> {
+        resources.iter().map(|r| r.id.as_str()).collect()
+    }
+}
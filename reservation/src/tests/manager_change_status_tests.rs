@@ -1,12 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use crate::{tests::test_utils::*, Rsvp};
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
 
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
     async fn change_pending_status_should_be_confirm() {
         let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
 
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
+        let rsvp = manager
+            .change_status(rsvp.id, None, "yangid")
+            .await
+            .unwrap();
 
         assert_eq!(rsvp.status, abi::ReservationStatus::Confirmed as i32);
     }
@@ -15,11 +18,44 @@ mod tests {
     async fn status_confirmed_update_status_should_do_nothing() {
         let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
 
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
+        let confirmed = manager
+            .change_status(rsvp.id, None, "yangid")
+            .await
+            .unwrap();
 
-        // update status again
-        let rsvp = manager.change_status(rsvp.id).await.unwrap_err();
+        // update status again, should return the same confirmed reservation
+        let again = manager
+            .change_status(rsvp.id, None, "yangid")
+            .await
+            .unwrap();
 
-        assert_eq!(rsvp, abi::Error::NotFound);
+        assert_eq!(again, confirmed);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn change_status_should_distinguish_not_found_and_not_pending() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+
+        let err = manager
+            .change_status(9999, None, "yangid")
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+
+        let mut rsvp = abi::Reservation::new_pending(
+            "yangid",
+            "Presidential-Suite",
+            "2022-12-25T15:00:00+0800".parse().unwrap(),
+            "2023-1-25T12:00:00+0800".parse().unwrap(),
+            "blocked for maintenance",
+        );
+        rsvp.status = abi::ReservationStatus::Blocked as i32;
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+
+        let err = manager
+            .change_status(rsvp.id, None, "yangid")
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotPending(abi::ReservationStatus::Blocked));
     }
 }
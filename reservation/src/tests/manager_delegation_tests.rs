@@ -0,0 +1,378 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{Delegation, DelegationAction, Error, Reservation, WaitlistEntry};
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn delegate_should_only_do_what_is_granted() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let on_behalf = Reservation {
+            created_by: "assistantid".to_string(),
+            ..make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00"))
+        };
+        assert_eq!(
+            manager.reserve(on_behalf.clone()).await,
+            Err(Error::NotDelegated(
+                "assistantid can't book for bossid".to_string()
+            ))
+        );
+
+        let delegation = manager
+            .set_delegation(
+                delegation(&[DelegationAction::Cancel, DelegationAction::Book]),
+                "bossid",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            delegation.actions,
+            vec![
+                DelegationAction::Book as i32,
+                DelegationAction::Cancel as i32
+            ]
+        );
+
+        // owner and booker are both kept
+        let rsvp = manager.reserve(on_behalf).await.unwrap();
+        assert_eq!(rsvp.user_id, "bossid");
+        assert_eq!(rsvp.created_by, "assistantid");
+
+        assert_eq!(
+            manager.change_status(rsvp.id, None, "assistantid").await,
+            Err(Error::NotDelegated(
+                "assistantid can't confirm for bossid".to_string()
+            ))
+        );
+        // a stranger can't do anything, the owner always can
+        assert!(matches!(
+            manager.delete(rsvp.id, None, "strangerid").await,
+            Err(Error::NotDelegated(_))
+        ));
+        manager
+            .change_status(rsvp.id, None, "bossid")
+            .await
+            .unwrap();
+        manager.delete(rsvp.id, None, "assistantid").await.unwrap();
+
+        assert_eq!(
+            changes_of(&migrated_pool, rsvp.id).await,
+            vec![
+                (
+                    "create".to_string(),
+                    "bossid".to_string(),
+                    "assistantid".to_string()
+                ),
+                (
+                    "update".to_string(),
+                    "bossid".to_string(),
+                    "bossid".to_string()
+                ),
+                (
+                    "delete".to_string(),
+                    "bossid".to_string(),
+                    "assistantid".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn empty_actions_should_revoke_the_delegation() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .set_delegation(delegation(&[DelegationAction::Book]), "bossid")
+            .await
+            .unwrap();
+        let revoked = manager
+            .set_delegation(delegation(&[]), "bossid")
+            .await
+            .unwrap();
+        assert!(revoked.actions.is_empty());
+        assert_eq!(
+            manager
+                .get_delegation("bossid".to_string(), "assistantid".to_string())
+                .await
+                .unwrap(),
+            revoked
+        );
+
+        let rsvp = Reservation {
+            created_by: "assistantid".to_string(),
+            ..make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00"))
+        };
+        assert!(matches!(
+            manager.reserve(rsvp).await,
+            Err(Error::NotDelegated(_))
+        ));
+
+        assert!(matches!(
+            manager
+                .set_delegation(
+                    Delegation {
+                        delegate_id: "bossid".to_string(),
+                        ..delegation(&[DelegationAction::Book])
+                    },
+                    "bossid"
+                )
+                .await,
+            Err(Error::InvalidDelegation(_))
+        ));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn only_the_owner_should_grant() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        // a stranger grants themselves booking for the owner
+        assert_eq!(
+            manager
+                .set_delegation(delegation(&[DelegationAction::Book]), "assistantid")
+                .await,
+            Err(Error::NotDelegated(
+                "assistantid can't delegate for bossid".to_string()
+            ))
+        );
+        assert!(matches!(
+            manager
+                .set_delegation(delegation(&[DelegationAction::Book]), "")
+                .await,
+            Err(Error::NotDelegated(_))
+        ));
+        assert_eq!(
+            manager
+                .get_delegation("bossid".to_string(), "assistantid".to_string())
+                .await
+                .unwrap(),
+            delegation(&[])
+        );
+        let rsvp = Reservation {
+            created_by: "assistantid".to_string(),
+            ..make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00"))
+        };
+        assert!(matches!(
+            manager.reserve(rsvp).await,
+            Err(Error::NotDelegated(_))
+        ));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn empty_actor_should_not_act_for_the_owner() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let required = |action: &str| {
+            Err(Error::NotDelegated(format!(
+                "acting user is required to {} for bossid",
+                action
+            )))
+        };
+        // no booker is the owner booking for themselves, as clients did before created_by
+        let rsvp = manager
+            .reserve(Reservation {
+                created_by: "".to_string(),
+                ..make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(rsvp.created_by, "bossid");
+        let entry = manager
+            .join_waitlist(WaitlistEntry::new(Reservation {
+                created_by: "".to_string(),
+                ..make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00"))
+            }))
+            .await
+            .unwrap();
+        assert_eq!(entry.reservation.unwrap().created_by, "bossid");
+
+        assert_eq!(
+            manager.change_status(rsvp.id, None, "").await,
+            required("confirm")
+        );
+        assert_eq!(manager.delete(rsvp.id, None, "").await, required("cancel"));
+        assert_eq!(
+            manager
+                .reschedule(rsvp.id, rsvp.start.clone(), rsvp.end.clone(), None, "")
+                .await,
+            required("book")
+        );
+        assert_eq!(
+            manager
+                .update_note(rsvp.id, "changed".to_string(), None, "")
+                .await,
+            required("book")
+        );
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn moving_for_the_owner_should_need_book() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = manager
+            .reserve(make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00")))
+            .await
+            .unwrap();
+        let moved = make_rsvp("bossid", "room-1", &at("12:00"), &at("13:00"));
+        assert_eq!(
+            manager
+                .reschedule(
+                    rsvp.id,
+                    moved.start.clone(),
+                    moved.end.clone(),
+                    None,
+                    "assistantid"
+                )
+                .await,
+            Err(Error::NotDelegated(
+                "assistantid can't book for bossid".to_string()
+            ))
+        );
+        assert!(matches!(
+            manager
+                .update_note(rsvp.id, "changed".to_string(), None, "assistantid")
+                .await,
+            Err(Error::NotDelegated(_))
+        ));
+
+        manager
+            .set_delegation(delegation(&[DelegationAction::Book]), "bossid")
+            .await
+            .unwrap();
+        let rsvp = manager
+            .reschedule(
+                rsvp.id,
+                moved.start.clone(),
+                moved.end.clone(),
+                None,
+                "assistantid",
+            )
+            .await
+            .unwrap();
+        assert_eq!(rsvp.start, moved.start);
+        manager
+            .update_note(rsvp.id, "changed".to_string(), None, "assistantid")
+            .await
+            .unwrap();
+        // the same note is no change
+        manager
+            .update_note(rsvp.id, "changed".to_string(), None, "bossid")
+            .await
+            .unwrap();
+
+        let change =
+            |op: &str, acted_by: &str| (op.to_string(), "bossid".to_string(), acted_by.to_string());
+        assert_eq!(
+            changes_of(&migrated_pool, rsvp.id).await,
+            vec![
+                change("create", "bossid"),
+                change("update", "assistantid"),
+                change("update", "assistantid"),
+            ]
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn waitlist_should_keep_the_booker() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let taken = manager
+            .reserve(make_rsvp("aliceid", "room-1", &at("10:00"), &at("11:00")))
+            .await
+            .unwrap();
+
+        let on_behalf = WaitlistEntry::new(Reservation {
+            created_by: "assistantid".to_string(),
+            ..make_rsvp("bossid", "room-1", &at("10:00"), &at("11:00"))
+        });
+        assert!(matches!(
+            manager.join_waitlist(on_behalf.clone()).await,
+            Err(Error::NotDelegated(_))
+        ));
+
+        manager
+            .set_delegation(delegation(&[DelegationAction::Book]), "bossid")
+            .await
+            .unwrap();
+        let entry = manager.join_waitlist(on_behalf).await.unwrap();
+        assert_eq!(
+            entry.reservation.as_ref().unwrap().created_by,
+            "assistantid"
+        );
+
+        manager.delete(taken.id, None, "aliceid").await.unwrap();
+        let entry = manager.get_waitlist_entry(entry.id).await.unwrap();
+        let promoted = manager.get(entry.reservation_id).await.unwrap();
+        assert_eq!(promoted.user_id, "bossid");
+        assert_eq!(promoted.created_by, "assistantid");
+        assert_eq!(
+            changes_of(&migrated_pool, promoted.id).await,
+            vec![(
+                "promote".to_string(),
+                "bossid".to_string(),
+                "assistantid".to_string()
+            )]
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn leaving_the_waitlist_should_need_book() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .reserve(make_rsvp("aliceid", "room-1", &at("10:00"), &at("11:00")))
+            .await
+            .unwrap();
+        let join = || async {
+            manager
+                .join_waitlist(WaitlistEntry::new(make_rsvp(
+                    "bossid",
+                    "room-1",
+                    &at("10:00"),
+                    &at("11:00"),
+                )))
+                .await
+                .unwrap()
+        };
+
+        // the owner
+        let entry = join().await;
+        assert_eq!(
+            manager.leave_waitlist(entry.id, "bossid").await.unwrap().id,
+            entry.id
+        );
+
+        // a stranger, and a delegate without BOOK
+        let entry = join().await;
+        assert_eq!(
+            manager.leave_waitlist(entry.id, "aliceid").await,
+            Err(Error::NotDelegated(
+                "aliceid can't book for bossid".to_string()
+            ))
+        );
+        manager
+            .set_delegation(delegation(&[DelegationAction::Cancel]), "bossid")
+            .await
+            .unwrap();
+        assert!(matches!(
+            manager.leave_waitlist(entry.id, "assistantid").await,
+            Err(Error::NotDelegated(_))
+        ));
+        assert_eq!(manager.get_waitlist_entry(entry.id).await.unwrap(), entry);
+
+        // a delegate with BOOK
+        manager
+            .set_delegation(delegation(&[DelegationAction::Book]), "bossid")
+            .await
+            .unwrap();
+        manager
+            .leave_waitlist(entry.id, "assistantid")
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_waitlist_entry(entry.id).await,
+            Err(Error::NotFound)
+        );
+    }
+
+    fn delegation(actions: &[DelegationAction]) -> Delegation {
+        Delegation {
+            owner_id: "bossid".to_string(),
+            delegate_id: "assistantid".to_string(),
+            actions: actions.iter().map(|a| *a as i32).collect(),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{ExclusionConfig, ReservationConflict, ReservationConflictInfo};
+    use sqlx::PgPool;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn same_user_on_two_exclusive_resources_should_reject() {
+        let manager = make_manager(migrated_pool.clone(), &["meeting-room"]).await;
+
+        let first = manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-07T01:00:00+0000",
+                "2030-01-07T03:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        let rsvp = make_rsvp(
+            "yangid",
+            "room-2",
+            "2030-01-07T02:00:00+0000",
+            "2030-01-07T04:00:00+0000",
+        );
+        let err = manager.reserve(rsvp.clone()).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::UserConflictReservation(ReservationConflictInfo::Parsed(
+                ReservationConflict {
+                    new: rsvp.window().unwrap(),
+                    old: first.window().unwrap(),
+                }
+            ))
+        );
+
+        // other users, other types and adjacent times are fine
+        manager
+            .reserve(make_rsvp(
+                "aliceid",
+                "room-1",
+                "2030-01-07T03:00:00+0000",
+                "2030-01-07T04:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "desk-1",
+                "2030-01-07T02:00:00+0000",
+                "2030-01-07T04:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        let later = manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-2",
+                "2030-01-07T03:00:00+0000",
+                "2030-01-07T04:00:00+0000",
+            ))
+            .await
+            .unwrap();
+
+        // moving onto the first one is rejected too
+        let err = manager
+            .reschedule(
+                later.id,
+                Some(first.start.clone().unwrap()),
+                None,
+                None,
+                "yangid",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::UserConflictReservation(_)));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn exclusion_should_be_opt_in() {
+        let manager = make_manager(migrated_pool.clone(), &[]).await;
+
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-07T01:00:00+0000",
+                "2030-01-07T03:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-2",
+                "2030-01-07T02:00:00+0000",
+                "2030-01-07T04:00:00+0000",
+            ))
+            .await
+            .unwrap();
+    }
+
+    // room-1 and room-2 are meeting rooms, desk-1 is a desk
+    async fn make_manager(pool: PgPool, types: &[&str]) -> ReservationManager {
+        let manager = ReservationManager::new(pool).with_exclusion_config(ExclusionConfig {
+            per_user_resource_types: types.iter().map(|t| t.to_string()).collect(),
+        });
+        set_resource_types(
+            &manager,
+            &[
+                ("room-1", "meeting-room"),
+                ("room-2", "meeting-room"),
+                ("desk-1", "desk"),
+            ],
+        )
+        .await;
+        manager
+    }
+}
@@ -2,6 +2,7 @@
 mod tests {
     use crate::{tests::test_utils::*, Rsvp};
     use abi::FilterByIdBuilder;
+    use prost_types::Timestamp;
 
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
     async fn test_filter_query_should_return_vec_of_reservation() {
@@ -21,4 +22,88 @@ mod tests {
         assert_eq!(rsvps.len(), 1);
         assert_eq!(rsvps[0], rsvp);
     }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn filter_with_time_window_should_only_return_reservations_within() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+
+        let filter = FilterByIdBuilder::default()
+            .user_id("yangid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .start("2022-12-01T00:00:00+0800".parse::<Timestamp>().unwrap())
+            .end("2023-02-01T00:00:00+0800".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+
+        let (_, rsvps) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(rsvps, vec![rsvp]);
+
+        // window is before the reservation, should return empty
+        let filter = FilterByIdBuilder::default()
+            .user_id("yangid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .start("2022-01-01T00:00:00+0800".parse::<Timestamp>().unwrap())
+            .end("2022-02-01T00:00:00+0800".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+
+        let (_, rsvps) = manager.keyset_query(filter).await.unwrap();
+        assert!(rsvps.is_empty());
+
+        // only end is given, start should be -infinity
+        let filter = FilterByIdBuilder::default()
+            .user_id("yangid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .end("2023-02-01T00:00:00+0800".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+
+        let (_, rsvps) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(rsvps.len(), 1);
+
+        // start after end should be rejected
+        let filter = FilterByIdBuilder::default()
+            .user_id("yangid")
+            .start("2023-02-01T00:00:00+0800".parse::<Timestamp>().unwrap())
+            .end("2022-12-01T00:00:00+0800".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+
+        let err = manager.keyset_query(filter).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn filter_with_multiple_user_ids_should_return_all_of_them() {
+        let (rsvp1, _) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+        let (rsvp2, manager) = make_reservation(
+            migrated_pool.clone(),
+            "aliceid",
+            "Ocean-View-Room",
+            "2022-12-25T15:00:00+0800",
+            "2022-12-26T12:00:00+0800",
+            "alice's room",
+        )
+        .await;
+
+        let filter = FilterByIdBuilder::default()
+            .user_ids(vec!["yangid".to_string(), "aliceid".to_string()])
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+
+        let (_, rsvps) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(rsvps, vec![rsvp1, rsvp2.clone()]);
+
+        // resource_ids narrows the result down
+        let filter = FilterByIdBuilder::default()
+            .user_ids(vec!["yangid".to_string(), "aliceid".to_string()])
+            .resource_ids(vec!["Ocean-View-Room".to_string()])
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+
+        let (_, rsvps) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(rsvps, vec![rsvp2]);
+    }
 }
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::{tests::test_utils::*, Rsvp};
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
 
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
     async fn test_getter_should_return_reservation() {
@@ -10,4 +10,30 @@ mod tests {
 
         assert_eq!(rsvp.status, abi::ReservationStatus::Pending as i32);
     }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn get_unbounded_reservation_should_have_no_end() {
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO rsvp.reservations (user_id, resource_id, timespan) VALUES ('yangid', 'desk-1', tstzrange('2023-01-01', 'infinity')) RETURNING id",
+        )
+        .fetch_one(&migrated_pool)
+        .await
+        .unwrap();
+
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = manager.get(id).await.unwrap();
+        assert!(rsvp.start.is_some());
+        assert_eq!(rsvp.end, None);
+
+        // NULL bound is unbounded as well
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO rsvp.reservations (user_id, resource_id, timespan) VALUES ('yangid', 'desk-2', tstzrange(NULL, '2023-01-01')) RETURNING id",
+        )
+        .fetch_one(&migrated_pool)
+        .await
+        .unwrap();
+        let rsvp = manager.get(id).await.unwrap();
+        assert_eq!(rsvp.start, None);
+        assert!(rsvp.end.is_some());
+    }
 }
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{ReservationConflict, ReservationConflictInfo, ResourceGroup};
+    use sqlx::PgPool;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn group_and_members_should_conflict_both_ways() {
+        let manager = make_manager(migrated_pool.clone()).await;
+
+        // member first, then the group
+        let member = manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-a",
+                "2030-01-07T10:00:00+0000",
+                "2030-01-07T11:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        let rsvp = make_rsvp(
+            "yangid",
+            "room-ab",
+            "2030-01-07T10:30:00+0000",
+            "2030-01-07T11:30:00+0000",
+        );
+        let err = manager.reserve(rsvp.clone()).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(ReservationConflict {
+                new: rsvp.window().unwrap(),
+                old: member.window().unwrap(),
+            }))
+        );
+
+        // group first, then a member
+        let group = manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-ab",
+                "2030-01-07T12:00:00+0000",
+                "2030-01-07T13:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        let rsvp = make_rsvp(
+            "yangid",
+            "room-b",
+            "2030-01-07T12:30:00+0000",
+            "2030-01-07T13:30:00+0000",
+        );
+        let err = manager.reserve(rsvp.clone()).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(ReservationConflict {
+                new: rsvp.window().unwrap(),
+                old: group.window().unwrap(),
+            }))
+        );
+
+        // members don't block each other, nested group blocks the members of its members
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-b",
+                "2030-01-07T10:00:00+0000",
+                "2030-01-07T11:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        let err = manager
+            .reserve(make_rsvp(
+                "yangid",
+                "floor-1",
+                "2030-01-07T10:59:00+0000",
+                "2030-01-07T11:30:00+0000",
+            ))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        // rescheduling onto the group is rejected, and the freed time can be taken
+        let err = manager
+            .reschedule(
+                member.id,
+                Some(group.start.clone().unwrap()),
+                None,
+                None,
+                "yangid",
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+        manager.delete(member.id, None, "yangid").await.unwrap();
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-a",
+                "2030-01-07T10:00:00+0000",
+                "2030-01-07T11:00:00+0000",
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn set_resource_group_should_check_existing_reservations() {
+        let manager = make_manager(migrated_pool.clone()).await;
+
+        let group = manager
+            .get_resource_group("floor-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(group.member_ids, vec!["room-ab", "room-c"]);
+
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-d",
+                "2030-01-07T10:00:00+0000",
+                "2030-01-07T11:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "floor-1",
+                "2030-01-07T10:00:00+0000",
+                "2030-01-07T11:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        let err = manager
+            .set_resource_group(make_group("floor-1", &["room-ab", "room-c", "room-d"]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+        let group = manager
+            .get_resource_group("floor-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(group.member_ids, vec!["room-ab", "room-c"]);
+
+        // a member can't contain its group again
+        let err = manager
+            .set_resource_group(make_group("room-a", &["floor-1"]))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceId("floor-1".to_string()));
+
+        // dissolving the group releases the members
+        manager
+            .set_resource_group(make_group("floor-1", &[]))
+            .await
+            .unwrap();
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-a",
+                "2030-01-07T10:00:00+0000",
+                "2030-01-07T11:00:00+0000",
+            ))
+            .await
+            .unwrap();
+    }
+
+    // room-ab is made of room-a and room-b, floor-1 is made of room-ab and room-c
+    async fn make_manager(pool: PgPool) -> ReservationManager {
+        let manager = ReservationManager::new(pool);
+        for group in [
+            make_group("room-ab", &["room-a", "room-b"]),
+            make_group("floor-1", &["room-c", "room-ab"]),
+        ] {
+            manager.set_resource_group(group).await.unwrap();
+        }
+        manager
+    }
+
+    fn make_group(id: &str, members: &[&str]) -> ResourceGroup {
+        ResourceGroup {
+            id: id.to_string(),
+            member_ids: members.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use crate::{ReservationManager, Rsvp};
+    use abi::{Error, IdempotencyConfig};
+    use std::time::Duration;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn claimed_key_should_replay_the_saved_response() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let claimed = manager
+            .claim_idempotency_key("reserve", "key-1", vec![1])
+            .await
+            .unwrap();
+        assert_eq!(claimed, None);
+
+        // the first request is not done yet
+        assert_eq!(
+            manager
+                .claim_idempotency_key("reserve", "key-1", vec![1])
+                .await,
+            Err(Error::IdempotencyKeyInProgress("key-1".to_string()))
+        );
+
+        manager
+            .save_idempotency_response("reserve", "key-1", vec![9, 9])
+            .await
+            .unwrap();
+        let replay = manager
+            .claim_idempotency_key("reserve", "key-1", vec![1])
+            .await
+            .unwrap();
+        assert_eq!(replay, Some(vec![9, 9]));
+
+        // saved responses are not released
+        manager
+            .release_idempotency_key("reserve", "key-1")
+            .await
+            .unwrap();
+        assert_eq!(
+            manager
+                .claim_idempotency_key("reserve", "key-1", vec![2])
+                .await,
+            Err(Error::IdempotencyKeyReused("key-1".to_string()))
+        );
+
+        // keys are scoped by method
+        let claimed = manager
+            .claim_idempotency_key("cancel", "key-1", vec![2])
+            .await
+            .unwrap();
+        assert_eq!(claimed, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn released_or_expired_key_should_be_claimed_again() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .claim_idempotency_key("reserve", "key-1", vec![1])
+            .await
+            .unwrap();
+        manager
+            .release_idempotency_key("reserve", "key-1")
+            .await
+            .unwrap();
+        // a failed request can be retried, even with a fixed payload
+        let claimed = manager
+            .claim_idempotency_key("reserve", "key-1", vec![2])
+            .await
+            .unwrap();
+        assert_eq!(claimed, None);
+        manager
+            .save_idempotency_response("reserve", "key-1", vec![9])
+            .await
+            .unwrap();
+
+        let manager = ReservationManager::new(migrated_pool.clone()).with_idempotency_config(
+            IdempotencyConfig {
+                ttl_seconds: 0,
+                ..Default::default()
+            },
+        );
+        let claimed = manager
+            .claim_idempotency_key("reserve", "key-1", vec![3])
+            .await
+            .unwrap();
+        assert_eq!(claimed, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn slow_request_should_keep_its_key() {
+        let manager = ReservationManager::new(migrated_pool.clone()).with_idempotency_config(
+            IdempotencyConfig {
+                lease_seconds: 1,
+                ..Default::default()
+            },
+        );
+        manager
+            .claim_idempotency_key("reserve", "key-1", vec![1])
+            .await
+            .unwrap();
+
+        // still running after the lease, the claim is renewed meanwhile
+        let retry = manager
+            .while_claimed("reserve", "key-1", async {
+                tokio::time::sleep(Duration::from_millis(2500)).await;
+                manager
+                    .claim_idempotency_key("reserve", "key-1", vec![1])
+                    .await
+            })
+            .await;
+        assert_eq!(
+            retry,
+            Err(Error::IdempotencyKeyInProgress("key-1".to_string()))
+        );
+
+        // nobody renews it (e.g. the server died), the lease runs out
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let claimed = manager
+            .claim_idempotency_key("reserve", "key-1", vec![1])
+            .await
+            .unwrap();
+        assert_eq!(claimed, None);
+    }
+}
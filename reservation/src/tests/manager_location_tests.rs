@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::{ReservationManager, Rsvp};
+    use abi::{FilterByIdBuilder, ReservationQueryBuilder, Resource};
+    use sqlx::PgPool;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_by_location_should_return_the_subtree() {
+        let (manager, rsvps) = make_campus(migrated_pool.clone()).await;
+
+        let query = ReservationQueryBuilder::default()
+            .location_id("building-3")
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, Some(Ok(rsvps[0].clone())));
+        assert_eq!(rx.recv().await, Some(Ok(rsvps[1].clone())));
+        assert_eq!(rx.recv().await, None);
+
+        // combined with resource ids
+        let query = ReservationQueryBuilder::default()
+            .location_id("campus")
+            .resource_id("room-101")
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, Some(Ok(rsvps[2].clone())));
+        assert_eq!(rx.recv().await, None);
+
+        let filter = FilterByIdBuilder::default()
+            .location_id("floor-3-2")
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let (_, found) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(found, vec![rsvps[1].clone()]);
+
+        // the resource itself is a location too
+        let filter = FilterByIdBuilder::default()
+            .location_id("room-101")
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let (_, found) = manager.keyset_query(filter).await.unwrap();
+        assert_eq!(found, vec![rsvps[2].clone()]);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn set_resource_should_reject_cycles() {
+        let (manager, _) = make_campus(migrated_pool.clone()).await;
+
+        let building = manager
+            .get_resource("building-3".to_string())
+            .await
+            .unwrap();
+        assert_eq!(building.parent_id, "campus");
+        // the parent is created as a root
+        let campus = manager.get_resource("campus".to_string()).await.unwrap();
+        assert_eq!(campus, Resource::root("campus"));
+        let unknown = manager.get_resource("room-999".to_string()).await.unwrap();
+        assert_eq!(unknown, Resource::root("room-999"));
+
+        let err = manager
+            .set_resource(make_resource("building-3", "room-302"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceId("room-302".to_string()));
+
+        // moving a room to another floor
+        let room = manager
+            .set_resource(make_resource("room-302", "floor-3-1"))
+            .await
+            .unwrap();
+        assert_eq!(room, make_resource("room-302", "floor-3-1"));
+    }
+
+    // campus > building-3 > floor-3-1 > room-301, campus > building-3 > floor-3-2 > room-302,
+    // campus > building-1 > room-101. one reservation on each room
+    async fn make_campus(pool: PgPool) -> (ReservationManager, Vec<abi::Reservation>) {
+        let manager = ReservationManager::new(pool);
+        for (id, parent_id) in [
+            ("building-3", "campus"),
+            ("floor-3-1", "building-3"),
+            ("floor-3-2", "building-3"),
+            ("room-301", "floor-3-1"),
+            ("room-302", "floor-3-2"),
+            ("building-1", "campus"),
+            ("room-101", "building-1"),
+        ] {
+            manager
+                .set_resource(make_resource(id, parent_id))
+                .await
+                .unwrap();
+        }
+
+        let mut rsvps = vec![];
+        for (rid, day) in [("room-301", 7), ("room-302", 8), ("room-101", 9)] {
+            let rsvp = abi::Reservation::new_pending(
+                "yangid",
+                rid,
+                format!("2030-01-{:02}T10:00:00+0000", day).parse().unwrap(),
+                format!("2030-01-{:02}T11:00:00+0000", day).parse().unwrap(),
+                "",
+            );
+            rsvps.push(manager.reserve(rsvp).await.unwrap());
+        }
+        (manager, rsvps)
+    }
+
+    fn make_resource(id: &str, parent_id: &str) -> Resource {
+        Resource {
+            id: id.to_string(),
+            parent_id: parent_id.to_string(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{Holiday, HolidayCalendar, OpeningHours, ResourcePolicy, SlotAdjustment};
+    use chrono::{Duration, Utc};
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn policy_should_default_to_unrestricted_and_be_replaceable() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+
+        let policy = manager.get_policy("room-1".to_string()).await.unwrap();
+        assert_eq!(policy, ResourcePolicy::unrestricted("room-1"));
+
+        let mut policy = make_policy(15, SlotAdjustment::Reject);
+        assert_eq!(manager.set_policy(policy.clone()).await.unwrap(), policy);
+
+        policy.slot_minutes = 30;
+        manager.set_policy(policy.clone()).await.unwrap();
+        assert_eq!(
+            manager.get_policy("room-1".to_string()).await.unwrap(),
+            policy
+        );
+
+        let err = manager.get_policy("".to_string()).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceId("".to_string()));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_should_reject_misaligned_time() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .set_policy(make_policy(15, SlotAdjustment::Reject))
+            .await
+            .unwrap();
+
+        let rsvp = abi::Reservation::new_pending(
+            "yangid",
+            "room-1",
+            "2023-01-01T10:03:17.123+0800".parse().unwrap(),
+            "2023-01-01T10:58:00+0800".parse().unwrap(),
+            "",
+        );
+        let err = manager.reserve(rsvp).await.unwrap_err();
+        assert_eq!(err, abi::Error::SlotMisaligned(15));
+
+        // other resources are not affected
+        let rsvp = abi::Reservation::new_pending(
+            "yangid",
+            "room-2",
+            "2023-01-01T10:03:17.123+0800".parse().unwrap(),
+            "2023-01-01T10:58:00+0800".parse().unwrap(),
+            "",
+        );
+        manager.reserve(rsvp).await.unwrap();
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_should_snap_misaligned_time() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .set_policy(make_policy(15, SlotAdjustment::Snap))
+            .await
+            .unwrap();
+
+        let rsvp = abi::Reservation::new_pending(
+            "yangid",
+            "room-1",
+            "2023-01-01T10:03:17.123+0800".parse().unwrap(),
+            "2023-01-01T10:58:00+0800".parse().unwrap(),
+            "",
+        );
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+
+        let expected = abi::Reservation::new_pending(
+            "yangid",
+            "room-1",
+            "2023-01-01T10:00:00+0800".parse().unwrap(),
+            "2023-01-01T11:00:00+0800".parse().unwrap(),
+            "",
+        );
+        assert_eq!(rsvp.start, expected.start);
+        assert_eq!(rsvp.end, expected.end);
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_should_check_duration_and_advance() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let policy = ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            min_duration_minutes: 15,
+            max_duration_minutes: 4 * 60,
+            min_advance_minutes: 60,
+            ..Default::default()
+        };
+        assert_eq!(manager.set_policy(policy.clone()).await.unwrap(), policy);
+
+        let start = Utc::now() + Duration::days(1);
+        let too_long = abi::Reservation::new_pending(
+            "yangid",
+            "room-1",
+            start.into(),
+            (start + Duration::days(3 * 365)).into(),
+            "",
+        );
+        let err = manager.reserve(too_long).await.unwrap_err();
+        assert_eq!(err, abi::Error::DurationTooLong(4 * 60));
+
+        let too_short = abi::Reservation::new_pending(
+            "yangid",
+            "room-1",
+            start.into(),
+            (start + Duration::seconds(5)).into(),
+            "",
+        );
+        let err = manager.reserve(too_short).await.unwrap_err();
+        assert_eq!(err, abi::Error::DurationTooShort(15));
+
+        let now = Utc::now();
+        let too_soon = abi::Reservation::new_pending(
+            "yangid",
+            "room-1",
+            now.into(),
+            (now + Duration::hours(1)).into(),
+            "",
+        );
+        let err = manager.reserve(too_soon).await.unwrap_err();
+        assert_eq!(err, abi::Error::StartTooSoon(60));
+
+        let rsvp = abi::Reservation::new_pending(
+            "yangid",
+            "room-1",
+            start.into(),
+            (start + Duration::hours(1)).into(),
+            "",
+        );
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+
+        // reschedule is checked by the same policy
+        let err = manager
+            .reschedule(rsvp.id, rsvp.start.clone(), None, None, "yangid")
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::DurationTooLong(4 * 60));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_should_respect_opening_hours_and_holidays() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let calendar = HolidayCalendar {
+            id: "tw".to_string(),
+            holidays: vec![Holiday {
+                date: "2023-01-02".to_string(),
+                name: "New Year".to_string(),
+            }],
+        };
+        assert_eq!(
+            manager.set_holidays(calendar.clone()).await.unwrap(),
+            calendar
+        );
+
+        // weekdays 08:00 - 20:00 in Taipei
+        let policy = ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            timezone: "Asia/Taipei".to_string(),
+            opening_hours: (1..=5)
+                .map(|weekday| OpeningHours {
+                    weekday,
+                    open_minute: 8 * 60,
+                    close_minute: 20 * 60,
+                })
+                .collect(),
+            holiday_calendars: vec!["tw".to_string()],
+            ..Default::default()
+        };
+        manager.set_policy(policy.clone()).await.unwrap();
+        assert_eq!(
+            manager.get_policy("room-1".to_string()).await.unwrap(),
+            policy
+        );
+
+        let cases = [
+            // monday, but holiday
+            ("2023-01-02T10:00:00+0800", "2023-01-02T11:00:00+0800"),
+            // tuesday night
+            ("2023-01-03T19:00:00+0800", "2023-01-03T21:00:00+0800"),
+            // saturday
+            ("2023-01-07T10:00:00+0800", "2023-01-07T11:00:00+0800"),
+        ];
+        for (start, end) in cases {
+            let rsvp = make_rsvp("yangid", "room-1", start, end);
+            let err = manager.reserve(rsvp).await.unwrap_err();
+            assert_eq!(err, abi::Error::OutsideOpeningHours);
+        }
+
+        let rsvp = make_rsvp(
+            "yangid",
+            "room-1",
+            "2023-01-03T08:00:00+0800",
+            "2023-01-03T20:00:00+0800",
+        );
+        manager.reserve(rsvp).await.unwrap();
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn invalid_policy_should_reject() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let policy = ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            timezone: "Mars/Olympus".to_string(),
+            ..Default::default()
+        };
+        let err = manager.set_policy(policy).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidPolicy("unknown timezone: Mars/Olympus".to_string())
+        );
+    }
+
+    fn make_policy(slot_minutes: i32, adjustment: SlotAdjustment) -> ResourcePolicy {
+        ResourcePolicy {
+            resource_id: "room-1".to_string(),
+            slot_minutes,
+            align_to_slot: true,
+            adjustment: adjustment as i32,
+            ..Default::default()
+        }
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use crate::{tests::test_utils::*, Rsvp};
-    use abi::ReservationQueryBuilder;
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{QueryConfig, ReservationQuery, ReservationQueryBuilder, TimeMatchMode};
     use prost_types::Timestamp;
+    use std::time::Duration;
 
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
     async fn test_query_should_return_vec_of_reservation() {
@@ -45,7 +46,10 @@ mod tests {
         assert_eq!(rx.recv().await, None);
 
         // change status should be queryable
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
+        let rsvp = manager
+            .change_status(rsvp.id, None, "yangid")
+            .await
+            .unwrap();
         let query = ReservationQueryBuilder::default()
             .user_id("yangid")
             .status(abi::ReservationStatus::Confirmed as i32)
@@ -57,4 +61,250 @@ mod tests {
         let mut rx = manager.query(query).await;
         assert_eq!(rx.recv().await, Some(Ok(rsvp)));
     }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_with_less_than_1ms_left_should_time_out() {
+        let (_rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+        let query = make_query_with_mode(
+            "2021-11-01T15:00:00+0800",
+            "2023-12-31T12:00:00+0800",
+            TimeMatchMode::Overlaps,
+        );
+
+        let mut rx = manager
+            .query_with_timeout(query, Some(Duration::from_micros(500)))
+            .await;
+        assert_eq!(rx.recv().await, Some(Err(abi::Error::QueryTimeout)));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_with_overlaps_mode_should_return_partially_covered_reservation() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+
+        // window starts in the middle of the reservation
+        let query = make_query_with_mode(
+            "2023-01-01T00:00:00+0800",
+            "2023-02-01T00:00:00+0800",
+            TimeMatchMode::Overlaps,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, Some(Ok(rsvp)));
+        assert_eq!(rx.recv().await, None);
+
+        // window is right after the reservation, [) range should not overlap
+        let query = make_query_with_mode(
+            "2023-01-25T12:00:00+0800",
+            "2023-02-01T00:00:00+0800",
+            TimeMatchMode::Overlaps,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_with_contained_mode_should_only_return_reservation_within_window() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+
+        let query = make_query_with_mode(
+            "2022-12-01T00:00:00+0800",
+            "2023-02-01T00:00:00+0800",
+            TimeMatchMode::Contained,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, Some(Ok(rsvp)));
+        assert_eq!(rx.recv().await, None);
+
+        // reservation starts before the window, should not be returned
+        let query = make_query_with_mode(
+            "2023-01-01T00:00:00+0800",
+            "2023-02-01T00:00:00+0800",
+            TimeMatchMode::Contained,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_with_contains_mode_should_return_reservation_covering_window() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+
+        // "what is happening in the room today"
+        let query = make_query_with_mode(
+            "2023-01-01T00:00:00+0800",
+            "2023-01-02T00:00:00+0800",
+            TimeMatchMode::Contains,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, Some(Ok(rsvp)));
+        assert_eq!(rx.recv().await, None);
+
+        // window is larger than the reservation, should not be returned
+        let query = make_query_with_mode(
+            "2022-12-01T00:00:00+0800",
+            "2023-02-01T00:00:00+0800",
+            TimeMatchMode::Contains,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_with_multiple_resource_ids_should_return_all_of_them() {
+        let mut rsvps = vec![];
+        for i in 101..=103 {
+            let (rsvp, _) = make_reservation(
+                migrated_pool.clone(),
+                "floor-manager",
+                &format!("room-{}", i),
+                "2022-12-25T15:00:00+0800",
+                "2022-12-25T16:00:00+0800",
+                "floor meeting",
+            )
+            .await;
+            rsvps.push(rsvp);
+        }
+        let manager = ReservationManager::new(migrated_pool.clone());
+
+        // resource_id is merged with resource_ids
+        let query = ReservationQueryBuilder::default()
+            .resource_id("room-101")
+            .resource_ids(vec!["room-102".to_string()])
+            .user_ids(vec!["floor-manager".to_string(), "someone".to_string()])
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+
+        let mut rx = manager.query(query).await;
+        let mut result = vec![];
+        while let Some(rsvp) = rx.recv().await {
+            result.push(rsvp.unwrap());
+        }
+        result.sort_by_key(|r| r.id);
+        assert_eq!(result, rsvps[..2]);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_should_respect_limit_and_report_server_truncation() {
+        for i in 0..3 {
+            make_reservation(
+                migrated_pool.clone(),
+                "yangid",
+                &format!("room-{}", i),
+                "2022-12-25T15:00:00+0800",
+                "2022-12-25T16:00:00+0800",
+                "limit test",
+            )
+            .await;
+        }
+        let manager =
+            ReservationManager::new(migrated_pool.clone()).with_query_config(QueryConfig {
+                max_rows: 2,
+                ..Default::default()
+            });
+
+        // client limit is smaller than server limit, no truncation reported
+        let query = ReservationQueryBuilder::default()
+            .user_id("yangid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .limit(1)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert!(matches!(rx.recv().await, Some(Ok(_))));
+        assert_eq!(rx.recv().await, None);
+
+        // no client limit, server limit is reached
+        let query = ReservationQueryBuilder::default()
+            .user_id("yangid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert!(matches!(rx.recv().await, Some(Ok(_))));
+        assert!(matches!(rx.recv().await, Some(Ok(_))));
+        assert_eq!(rx.recv().await, Some(Err(abi::Error::QueryTruncated(2))));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_window_larger_than_config_should_reject() {
+        let manager =
+            ReservationManager::new(migrated_pool.clone()).with_query_config(QueryConfig {
+                max_window_days: Some(7),
+                ..Default::default()
+            });
+
+        let query = make_query_with_mode(
+            "2023-01-01T00:00:00+0800",
+            "2023-01-31T00:00:00+0800",
+            TimeMatchMode::Overlaps,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(
+            rx.recv().await,
+            Some(Err(abi::Error::QueryWindowTooLarge(7)))
+        );
+        assert_eq!(rx.recv().await, None);
+
+        // open ended window is rejected as well
+        let query = ReservationQueryBuilder::default()
+            .user_id("yangid")
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(
+            rx.recv().await,
+            Some(Err(abi::Error::QueryWindowTooLarge(7)))
+        );
+
+        let query = make_query_with_mode(
+            "2023-01-01T00:00:00+0800",
+            "2023-01-02T00:00:00+0800",
+            TimeMatchMode::Overlaps,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn query_should_return_open_ended_reservation_by_mode() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = abi::Reservation::new_open_ended(
+            "yangid",
+            "desk-1",
+            "2023-01-02T09:00:00+0800".parse().unwrap(),
+            "assigned desk",
+        );
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+
+        // any window after the start is covered by an open-ended reservation
+        for mode in [TimeMatchMode::Overlaps, TimeMatchMode::Contains] {
+            let query =
+                make_query_with_mode("2030-01-01T00:00:00+0800", "2030-01-02T00:00:00+0800", mode);
+            let mut rx = manager.query(query).await;
+            assert_eq!(rx.recv().await, Some(Ok(rsvp.clone())));
+            assert_eq!(rx.recv().await, None);
+        }
+
+        // but it never fits in a bounded window
+        let query = make_query_with_mode(
+            "2023-01-01T00:00:00+0800",
+            "2030-01-01T00:00:00+0800",
+            TimeMatchMode::Contained,
+        );
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, None);
+    }
+
+    fn make_query_with_mode(start: &str, end: &str, mode: TimeMatchMode) -> ReservationQuery {
+        ReservationQueryBuilder::default()
+            .user_id("yangid")
+            .status(abi::ReservationStatus::Pending as i32)
+            .start(start.parse::<Timestamp>().unwrap())
+            .end(end.parse::<Timestamp>().unwrap())
+            .match_mode(mode as i32)
+            .build()
+            .unwrap()
+    }
 }
@@ -0,0 +1,258 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{QuotaConfig, QuotaUsage, ResourcePolicy};
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_over_active_quota_should_reject_with_usage() {
+        let manager = make_manager(
+            migrated_pool.clone(),
+            QuotaConfig {
+                max_active_reservations: Some(2),
+                ..Default::default()
+            },
+        );
+
+        for i in 0..2 {
+            manager
+                .reserve(make_rsvp_from_now("yangid", "room-1", i, 1))
+                .await
+                .unwrap();
+        }
+        let err = manager
+            .reserve(make_rsvp_from_now("yangid", "room-1", 2, 1))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::QuotaExceeded(QuotaUsage {
+                quota: "active_reservations".to_string(),
+                used: 2,
+                limit: 2,
+            })
+        );
+
+        // finished reservations are not counted
+        let past = abi::Reservation::new_pending(
+            "aliceid",
+            "room-2",
+            (Utc::now() - Duration::days(2)).into(),
+            (Utc::now() - Duration::days(1)).into(),
+            "",
+        );
+        manager.reserve(past).await.unwrap();
+        for i in 0..2 {
+            manager
+                .reserve(make_rsvp_from_now("aliceid", "room-2", i, 1))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_over_weekly_hours_should_reject() {
+        let manager = make_manager(
+            migrated_pool.clone(),
+            QuotaConfig {
+                max_hours_per_week: Some(3),
+                ..Default::default()
+            },
+        );
+
+        // monday 2 hours, then 1 hour is fine, 1 more is over
+        let monday = "2030-01-07T01:00:00+0000";
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                monday,
+                "2030-01-07T03:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-08T01:00:00+0000",
+                "2030-01-08T02:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        let err = manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-09T01:00:00+0000",
+                "2030-01-09T02:00:00+0000",
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::QuotaExceeded(QuotaUsage {
+                quota: "minutes_per_week".to_string(),
+                used: 180,
+                limit: 180,
+            })
+        );
+
+        // next week is a new quota
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-14T01:00:00+0000",
+                "2030-01-14T02:00:00+0000",
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn weekly_hours_should_count_every_local_week() {
+        let manager = make_manager(
+            migrated_pool.clone(),
+            QuotaConfig {
+                max_hours_per_week: Some(3),
+                ..Default::default()
+            },
+        );
+        // room-1 weeks start at monday 00:00 in Taipei, sunday 16:00 UTC
+        manager
+            .set_policy(ResourcePolicy {
+                resource_id: "room-1".to_string(),
+                timezone: "Asia/Taipei".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // 3 hours on monday 2030-01-14 local, sunday 2030-01-13 in UTC
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-13T16:00:00+0000",
+                "2030-01-13T19:00:00+0000",
+            ))
+            .await
+            .unwrap();
+        // the local week before is free, but this one crosses into the full week
+        let err = manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-13T15:00:00+0000",
+                "2030-01-13T16:30:00+0000",
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::QuotaExceeded(QuotaUsage {
+                quota: "minutes_per_week".to_string(),
+                used: 180,
+                limit: 180,
+            })
+        );
+        // in the local week before only
+        manager
+            .reserve(make_rsvp(
+                "yangid",
+                "room-1",
+                "2030-01-13T13:00:00+0000",
+                "2030-01-13T16:00:00+0000",
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_over_resource_type_quota_should_reject() {
+        let manager = make_manager(
+            migrated_pool.clone(),
+            QuotaConfig {
+                max_active_per_resource_type: [("meeting-room".to_string(), 1)].into(),
+                ..Default::default()
+            },
+        );
+        for rid in ["room-1", "room-2"] {
+            manager
+                .set_policy(ResourcePolicy {
+                    resource_id: rid.to_string(),
+                    resource_type: "meeting-room".to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        manager
+            .reserve(make_rsvp_from_now("yangid", "room-1", 0, 1))
+            .await
+            .unwrap();
+        let err = manager
+            .reserve(make_rsvp_from_now("yangid", "room-2", 1, 1))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::QuotaExceeded(QuotaUsage {
+                quota: "active_reservations:meeting-room".to_string(),
+                used: 1,
+                limit: 1,
+            })
+        );
+
+        // a desk has no type quota
+        manager
+            .reserve(make_rsvp_from_now("yangid", "desk-1", 1, 1))
+            .await
+            .unwrap();
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn concurrent_reserve_should_not_exceed_quota() {
+        let quota = QuotaConfig {
+            max_active_reservations: Some(1),
+            ..Default::default()
+        };
+
+        let mut handles = vec![];
+        for i in 0..5 {
+            let manager = make_manager(migrated_pool.clone(), quota.clone());
+            handles.push(tokio::spawn(async move {
+                manager
+                    .reserve(make_rsvp_from_now("yangid", &format!("room-{}", i), 0, 1))
+                    .await
+            }));
+        }
+
+        let mut succeeded = 0;
+        for handle in handles {
+            if handle.await.unwrap().is_ok() {
+                succeeded += 1;
+            }
+        }
+        assert_eq!(succeeded, 1);
+    }
+
+    fn make_manager(pool: PgPool, quota: QuotaConfig) -> ReservationManager {
+        ReservationManager::new(pool).with_quota_config(quota)
+    }
+
+    // starts `day` days later, lasts `hours` hours
+    fn make_rsvp_from_now(uid: &str, rid: &str, day: i64, hours: i64) -> abi::Reservation {
+        let start = Utc::now() + Duration::days(day + 1);
+        abi::Reservation::new_pending(
+            uid,
+            rid,
+            start.into(),
+            (start + Duration::hours(hours)).into(),
+            "",
+        )
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::{ReservationConflict, ReservationConflictInfo};
+    use prost_types::Timestamp;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reschedule_should_move_reservation() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+
+        // overlapping with its own old timespan is fine
+        let start: Timestamp = "2022-12-26T15:00:00+08:00".parse().unwrap();
+        let end: Timestamp = "2023-01-26T12:00:00+08:00".parse().unwrap();
+        let moved = manager
+            .reschedule(
+                rsvp.id,
+                Some(start.clone()),
+                Some(end.clone()),
+                None,
+                "yangid",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(moved.id, rsvp.id);
+        assert_eq!(moved.start, Some(start));
+        assert_eq!(moved.end, Some(end));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), moved);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reschedule_into_other_reservation_should_conflict() {
+        let (rsvp1, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+        let (rsvp2, _) = make_reservation(
+            migrated_pool.clone(),
+            "aliceid",
+            "Presidential-Suite",
+            "2023-02-01T15:00:00+0800",
+            "2023-02-03T12:00:00+0800",
+            "",
+        )
+        .await;
+
+        let err = manager
+            .reschedule(
+                rsvp2.id,
+                rsvp1.start.clone(),
+                rsvp2.end.clone(),
+                None,
+                "aliceid",
+            )
+            .await
+            .unwrap_err();
+
+        let mut moved = rsvp2.clone();
+        moved.start = rsvp1.start.clone();
+        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+            new: moved.window().unwrap(),
+            old: rsvp1.window().unwrap(),
+        });
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reschedule_with_invalid_input_should_reject() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+
+        let err = manager
+            .reschedule(
+                rsvp.id,
+                rsvp.end.clone(),
+                rsvp.start.clone(),
+                None,
+                "yangid",
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
+
+        let err = manager
+            .reschedule(
+                rsvp.id + 1000,
+                rsvp.start.clone(),
+                rsvp.end.clone(),
+                None,
+                "yangid",
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let err = manager
+            .reschedule(0, None, None, None, "yangid")
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidReservationId(0));
+    }
+}
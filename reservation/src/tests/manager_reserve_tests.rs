@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::{tests::test_utils::*, Rsvp};
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
     use abi::{ReservationConflict, ReservationConflictInfo, ReservationWindow};
 
     #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
@@ -26,17 +26,127 @@ mod tests {
         let info = ReservationConflictInfo::Parsed(ReservationConflict {
             new: ReservationWindow {
                 rid: "Presidential-Suite".to_string(),
-                start: "2022-12-26T15:00:00+0800".parse().unwrap(),
-                end: "2022-12-30T12:00:00+0800".parse().unwrap(),
+                start: Some("2022-12-26T15:00:00+0800".parse().unwrap()),
+                end: Some("2022-12-30T12:00:00+0800".parse().unwrap()),
             },
 
             old: ReservationWindow {
                 rid: "Presidential-Suite".to_string(),
-                start: "2022-12-25T15:00:00+0800".parse().unwrap(),
-                end: "2023-1-25T12:00:00+0800".parse().unwrap(),
+                start: Some("2022-12-25T15:00:00+0800".parse().unwrap()),
+                end: Some("2023-1-25T12:00:00+0800".parse().unwrap()),
             },
         });
 
         assert_eq!(err, abi::Error::ConflictReservation(info));
     }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_conflict_with_any_resource_id_should_be_parsed() {
+        let rid = "bldg:3 / room 7.1 (會議室)";
+        let (rsvp1, manager) = make_reservation(
+            migrated_pool.clone(),
+            "yangid",
+            rid,
+            "2022-12-25T15:00:00+0800",
+            "2022-12-25T16:00:00+0800",
+            "",
+        )
+        .await;
+
+        let rsvp2 = abi::Reservation::new_pending(
+            "aliceid",
+            rid,
+            "2022-12-25T15:30:00+0800".parse().unwrap(),
+            "2022-12-25T16:30:00+0800".parse().unwrap(),
+            "",
+        );
+        let err = manager.reserve(rsvp2.clone()).await.unwrap_err();
+
+        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+            new: rsvp2.window().unwrap(),
+            old: rsvp1.window().unwrap(),
+        });
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_with_missing_or_invalid_time_should_reject() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = abi::Reservation::new_pending(
+            "yangid",
+            "Presidential-Suite",
+            "2022-12-25T15:00:00+0800".parse().unwrap(),
+            "2023-1-25T12:00:00+0800".parse().unwrap(),
+            "",
+        );
+
+        let mut missing_start = rsvp.clone();
+        missing_start.start = None;
+        let err = manager.reserve(missing_start).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
+
+        let mut invalid_start = rsvp;
+        invalid_start.start = Some(prost_types::Timestamp {
+            seconds: i64::MIN,
+            nanos: -1,
+        });
+        let err = manager.reserve(invalid_start).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_open_ended_should_work() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = abi::Reservation::new_open_ended(
+            "yangid",
+            "desk-1",
+            "2023-01-02T09:00:00+0800".parse().unwrap(),
+            "assigned desk",
+        );
+
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+        assert!(rsvp.id != 0);
+        assert_eq!(rsvp.end, None);
+
+        let rsvp = manager.get(rsvp.id).await.unwrap();
+        assert_eq!(rsvp.end, None);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reserve_after_open_ended_should_conflict() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp1 = abi::Reservation::new_open_ended(
+            "yangid",
+            "desk-1",
+            "2023-01-02T09:00:00+0800".parse().unwrap(),
+            "assigned desk",
+        );
+        let rsvp1 = manager.reserve(rsvp1).await.unwrap();
+
+        // a year later is still taken by the open-ended reservation
+        let rsvp2 = abi::Reservation::new_pending(
+            "aliceid",
+            "desk-1",
+            "2024-01-02T09:00:00+0800".parse().unwrap(),
+            "2024-01-02T18:00:00+0800".parse().unwrap(),
+            "",
+        );
+        let err = manager.reserve(rsvp2.clone()).await.unwrap_err();
+
+        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+            new: rsvp2.window().unwrap(),
+            old: rsvp1.window().unwrap(),
+        });
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+
+        // but before it starts is free
+        let rsvp3 = abi::Reservation::new_pending(
+            "aliceid",
+            "desk-1",
+            "2023-01-01T09:00:00+0800".parse().unwrap(),
+            "2023-01-01T18:00:00+0800".parse().unwrap(),
+            "",
+        );
+        manager.reserve(rsvp3).await.unwrap();
+    }
 }
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use crate::manager::retry_on_abort;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn aborted_transaction_should_be_retried() {
+        let calls = AtomicU32::new(0);
+
+        let ret = retry_on_abort(|| async {
+            // aborted twice, then succeed
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(abi::Error::DbAborted(sqlx::Error::PoolTimedOut))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+
+        assert_eq!(ret.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_should_be_bounded_and_skip_other_errors() {
+        let calls = AtomicU32::new(0);
+        let ret: Result<(), _> = retry_on_abort(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(abi::Error::DbAborted(sqlx::Error::PoolTimedOut))
+        })
+        .await;
+        assert_eq!(
+            ret.unwrap_err(),
+            abi::Error::DbAborted(sqlx::Error::PoolTimedOut)
+        );
+        // first call + 3 retries
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        let calls = AtomicU32::new(0);
+        let ret: Result<(), _> = retry_on_abort(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(abi::Error::DbUnavailable(sqlx::Error::PoolTimedOut))
+        })
+        .await;
+        assert!(ret.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -8,6 +8,8 @@ mod tests {
         let rsvp = manager.update_note(
             rsvp.id,
             "I spent all of my money so plz gives me a wonderful feeling. I want to have a wonderful experience.".to_string(),
+            None,
+            "yangid",
         ).await.unwrap();
 
         assert_eq!(rsvp.note, "I spent all of my money so plz gives me a wonderful feeling. I want to have a wonderful experience.");
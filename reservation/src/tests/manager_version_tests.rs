@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, Rsvp};
+    use abi::Error;
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn version_should_go_up_on_every_change() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+        assert_eq!(rsvp.version, 1);
+
+        let rsvp = manager
+            .update_note(rsvp.id, "first".to_string(), Some(1), "yangid")
+            .await
+            .unwrap();
+        assert_eq!(rsvp.version, 2);
+        let rsvp = manager
+            .change_status(rsvp.id, Some(2), "yangid")
+            .await
+            .unwrap();
+        assert_eq!(rsvp.version, 3);
+        assert_eq!(manager.get(rsvp.id).await.unwrap().version, 3);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn stale_version_should_fail_with_the_current_reservation() {
+        let (rsvp, manager) = make_reservation_with_yang_template(migrated_pool.clone()).await;
+        // the other admin wins
+        let current = manager
+            .update_note(rsvp.id, "admin a".to_string(), Some(rsvp.version), "yangid")
+            .await
+            .unwrap();
+        let mismatch = Err(Error::VersionMismatch(Box::new(current.clone())));
+
+        assert_eq!(
+            manager
+                .update_note(rsvp.id, "admin b".to_string(), Some(rsvp.version), "yangid")
+                .await,
+            mismatch
+        );
+        assert_eq!(
+            manager
+                .change_status(rsvp.id, Some(rsvp.version), "yangid")
+                .await,
+            mismatch
+        );
+        assert_eq!(
+            manager
+                .reschedule(
+                    rsvp.id,
+                    rsvp.start.clone(),
+                    None,
+                    Some(rsvp.version),
+                    "yangid",
+                )
+                .await,
+            mismatch
+        );
+        assert_eq!(
+            manager.delete(rsvp.id, Some(rsvp.version), "yangid").await,
+            mismatch
+        );
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), current);
+
+        // no version, last writer wins as before
+        let rsvp = manager
+            .update_note(rsvp.id, "admin b".to_string(), None, "yangid")
+            .await
+            .unwrap();
+        manager
+            .delete(rsvp.id, Some(rsvp.version), "yangid")
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.delete(rsvp.id, Some(rsvp.version), "yangid").await,
+            Err(Error::NotFound)
+        );
+    }
+}
@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use crate::{tests::test_utils::*, ReservationManager, Rsvp};
+    use abi::WaitlistEntry;
+    use chrono::{Duration, Utc};
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn cancel_should_promote_the_first_fitting_entry() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let taken = manager
+            .reserve(make_rsvp("aliceid", "room-1", &at("10:00"), &at("11:00")))
+            .await
+            .unwrap();
+
+        let first = manager
+            .join_waitlist(WaitlistEntry::new(make_rsvp(
+                "yangid",
+                "room-1",
+                &at("10:00"),
+                &at("11:00"),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(first.reservation_id, 0);
+        let second = manager
+            .join_waitlist(WaitlistEntry::new(make_rsvp(
+                "bobid",
+                "room-1",
+                &at("10:30"),
+                &at("11:00"),
+            )))
+            .await
+            .unwrap();
+
+        manager.delete(taken.id, None, "aliceid").await.unwrap();
+
+        // bob overlaps yang, so keeps waiting
+        let first = manager.get_waitlist_entry(first.id).await.unwrap();
+        let promoted = manager.get(first.reservation_id).await.unwrap();
+        assert_eq!(promoted.user_id, "yangid");
+        assert_eq!(promoted.status, abi::ReservationStatus::Pending as i32);
+        assert_eq!(
+            changes_of(&migrated_pool, promoted.id).await,
+            vec![(
+                "promote".to_string(),
+                "yangid".to_string(),
+                "yangid".to_string()
+            )]
+        );
+        let second = manager.get_waitlist_entry(second.id).await.unwrap();
+        assert_eq!(second.reservation_id, 0);
+
+        // a cancelled promotion is not promoted again, bob is next
+        manager.delete(promoted.id, None, "yangid").await.unwrap();
+        let first = manager.get_waitlist_entry(first.id).await.unwrap();
+        assert_eq!(first.reservation_id, 0);
+        let second = manager.get_waitlist_entry(second.id).await.unwrap();
+        assert_eq!(
+            manager.get(second.reservation_id).await.unwrap().user_id,
+            "bobid"
+        );
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn reschedule_should_promote_the_freed_range() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let taken = manager
+            .reserve(make_rsvp("aliceid", "room-1", &at("10:00"), &at("11:00")))
+            .await
+            .unwrap();
+        let entry = manager
+            .join_waitlist(WaitlistEntry::new(make_rsvp(
+                "yangid",
+                "room-1",
+                &at("10:00"),
+                &at("11:00"),
+            )))
+            .await
+            .unwrap();
+
+        let moved = make_rsvp("aliceid", "room-1", &at("12:00"), &at("13:00"));
+        manager
+            .reschedule(taken.id, moved.start, moved.end, None, "aliceid")
+            .await
+            .unwrap();
+        let entry = manager.get_waitlist_entry(entry.id).await.unwrap();
+        assert_ne!(entry.reservation_id, 0);
+    }
+
+    #[sqlx_database_tester::test(pool(variable = "migrated_pool", migrations = "../migrations"))]
+    async fn expired_or_left_entries_should_not_be_promoted() {
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let taken = manager
+            .reserve(make_rsvp("aliceid", "room-1", &at("10:00"), &at("11:00")))
+            .await
+            .unwrap();
+
+        let expired = manager
+            .join_waitlist(WaitlistEntry {
+                expires_at: Some(abi::convert_to_timestamp(Utc::now() - Duration::minutes(1))),
+                ..WaitlistEntry::new(make_rsvp("yangid", "room-1", &at("10:00"), &at("11:00")))
+            })
+            .await
+            .unwrap();
+        let left = manager
+            .join_waitlist(WaitlistEntry::new(make_rsvp(
+                "bobid",
+                "room-1",
+                &at("10:00"),
+                &at("11:00"),
+            )))
+            .await
+            .unwrap();
+        manager.leave_waitlist(left.id, "bobid").await.unwrap();
+        assert_eq!(
+            manager.get_waitlist_entry(left.id).await.unwrap_err(),
+            abi::Error::NotFound
+        );
+
+        manager.delete(taken.id, None, "aliceid").await.unwrap();
+        let expired = manager.get_waitlist_entry(expired.id).await.unwrap();
+        assert_eq!(expired.reservation_id, 0);
+
+        // free already, promoted at once
+        let entry = manager
+            .join_waitlist(WaitlistEntry::new(make_rsvp(
+                "carolid",
+                "room-1",
+                &at("10:00"),
+                &at("11:00"),
+            )))
+            .await
+            .unwrap();
+        assert_ne!(entry.reservation_id, 0);
+    }
+}
@@ -1,16 +1,32 @@
+mod manager_alternatives_tests;
+mod manager_audit_tests;
+mod manager_available_tests;
 mod manager_change_status_tests;
+mod manager_delegation_tests;
 mod manager_delete_tests;
+mod manager_exclusion_tests;
 mod manager_filter_tests;
 mod manager_get_tests;
+mod manager_group_tests;
+mod manager_idempotency_tests;
+mod manager_location_tests;
+mod manager_policy_tests;
 mod manager_query_tests;
+mod manager_quota_tests;
+mod manager_reschedule_tests;
 mod manager_reserve_tests;
+mod manager_retry_tests;
 mod manager_update_note_tests;
+mod manager_version_tests;
+mod manager_waitlist_tests;
 
 #[cfg(test)]
 mod test_utils {
     use crate::{ReservationManager, Rsvp};
-    use abi::Reservation;
+    use abi::{Reservation, ResourcePolicy};
+    use chrono::{Duration, Utc};
     use sqlx::PgPool;
+    use std::sync::OnceLock;
 
     pub async fn make_reservation_with_yang_template(
         pool: PgPool,
@@ -45,4 +61,46 @@ mod test_utils {
 
         (manager.reserve(rsvp).await.unwrap(), manager)
     }
+
+    // a pending reservation without note, start and end are timestamps like "2030-01-07T10:00:00+0000"
+    pub fn make_rsvp(uid: &str, rid: &str, start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(uid, rid, start.parse().unwrap(), end.parse().unwrap(), "")
+    }
+
+    // give each resource a type by its policy, e.g. ("room-1", "meeting-room")
+    pub async fn set_resource_types(manager: &ReservationManager, types: &[(&str, &str)]) {
+        for (rid, resource_type) in types {
+            manager
+                .set_policy(ResourcePolicy {
+                    resource_id: rid.to_string(),
+                    resource_type: resource_type.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    // "HH:MM" (UTC) on a day 30 days from now, the same day for the whole test run.
+    // for tests which compare with the current time, e.g. no suggestion in the past
+    pub fn at(time: &str) -> String {
+        static DAY: OnceLock<String> = OnceLock::new();
+        let day = DAY.get_or_init(|| {
+            (Utc::now() + Duration::days(30))
+                .format("%Y-%m-%d")
+                .to_string()
+        });
+        format!("{}T{}:00+0000", day, time)
+    }
+
+    // (op, user_id, acted_by) of the recorded changes of a reservation, oldest first
+    pub async fn changes_of(pool: &PgPool, id: i64) -> Vec<(String, String, String)> {
+        sqlx::query_as(
+            "SELECT op::text, user_id, acted_by FROM rsvp.reservation_changes WHERE reservation_id = $1 ORDER BY id",
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
 }
@@ -5,20 +5,86 @@
 use crate::{ReservationStream, RsvpService};
 use abi::{
     reservation_service_server::ReservationService, CancelRequest, CancelResponse, Config,
-    ConfirmRequest, ConfirmResponse, FilterRequest, FilterResponse, GetRequest, GetResponse,
-    ListenRequest, QueryRequest, ReserveRequest, ReserveResponse, UpdateRequest, UpdateResponse,
+    ConfirmRequest, ConfirmResponse, FilterRequest, FilterResponse, FindAvailableRequest,
+    FindAvailableResponse, GetDelegationRequest, GetDelegationResponse, GetHolidaysRequest,
+    GetHolidaysResponse, GetPolicyRequest, GetPolicyResponse, GetRequest, GetResourceGroupRequest,
+    GetResourceGroupResponse, GetResourceRequest, GetResourceResponse, GetResponse,
+    GetWaitlistEntryRequest, GetWaitlistEntryResponse, Idempotent, JoinWaitlistRequest,
+    JoinWaitlistResponse, LeaveWaitlistRequest, LeaveWaitlistResponse, ListenRequest, QueryRequest,
+    RescheduleRequest, RescheduleResponse, ReservationAlternatives, ReserveRequest,
+    ReserveResponse, SetDelegationRequest, SetDelegationResponse, SetHolidaysRequest,
+    SetHolidaysResponse, SetPolicyRequest, SetPolicyResponse, SetResourceGroupRequest,
+    SetResourceGroupResponse, SetResourceRequest, SetResourceResponse, StatusDetail, UpdateRequest,
+    UpdateResponse,
 };
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 use reservation::{ReservationManager, Rsvp};
+use std::{future::Future, time::Duration};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{async_trait, Request, Response, Status};
+use tracing::warn;
 
 impl RsvpService {
     pub async fn from_config(config: &Config) -> Result<Self, anyhow::Error> {
         Ok(Self {
-            manager: ReservationManager::from_config(&config.db).await?,
+            manager: ReservationManager::from_config(&config.db)
+                .await?
+                .with_query_config(config.query.clone())
+                .with_quota_config(config.quota.clone())
+                .with_exclusion_config(config.exclusion.clone())
+                .with_idempotency_config(config.idempotency.clone()),
         })
     }
+
+    // run the request once per idempotency key, a retry gets the stored response.
+    // failed requests are not stored, so the client can retry them with the same key
+    async fn idempotent<R, F, Fut>(
+        &self,
+        request: R,
+        handle: F,
+    ) -> std::result::Result<Response<R::Response>, Status>
+    where
+        R: Idempotent,
+        F: FnOnce(R) -> Fut,
+        Fut: Future<Output = std::result::Result<R::Response, Status>>,
+    {
+        let key = request.idempotency_key().to_string();
+        if key.is_empty() {
+            return handle(request).await.map(Response::new);
+        }
+
+        let stored = self
+            .manager
+            .claim_idempotency_key(R::METHOD, &key, request.fingerprint())
+            .await?;
+        if let Some(stored) = stored {
+            return Ok(Response::new(R::decode_response(&stored)?));
+        }
+
+        match self
+            .manager
+            .while_claimed(R::METHOD, &key, handle(request))
+            .await
+        {
+            Ok(response) => {
+                // the change is done already, a retry after this fails on the in progress key until it's freed
+                if let Err(e) = self
+                    .manager
+                    .save_idempotency_response(R::METHOD, &key, R::encode_response(&response))
+                    .await
+                {
+                    warn!("failed to save response of idempotency key {}: {}", key, e);
+                }
+                Ok(Response::new(response))
+            }
+            Err(status) => {
+                if let Err(e) = self.manager.release_idempotency_key(R::METHOD, &key).await {
+                    warn!("failed to release idempotency key {}: {}", key, e);
+                }
+                Err(status)
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -28,26 +94,47 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<ReserveRequest>,
     ) -> std::result::Result<Response<ReserveResponse>, Status> {
-        let request = request.into_inner();
-        if request.reservation.is_none() {
-            return Err(Status::invalid_argument("reservation is required"));
-        }
-        let reservation = self.manager.reserve(request.reservation.unwrap()).await?;
-        Ok(Response::new(ReserveResponse {
-            reservation: Some(reservation),
-        }))
+        self.idempotent(request.into_inner(), |request| async move {
+            let reservation = request
+                .reservation
+                .ok_or_else(|| Status::invalid_argument("reservation is required"))?;
+            let reservation = match self.manager.reserve(reservation.clone()).await {
+                Err(e @ abi::Error::ConflictReservation(_)) if request.suggest_alternatives => {
+                    let alternatives = self
+                        .manager
+                        .suggest_alternatives(reservation, request.include_similar_resources)
+                        .await?;
+                    // keep the code, message and metadata of the conflict, the alternatives go into the details
+                    return Err(ReservationAlternatives { alternatives }.attach_to_status(e.into()));
+                }
+                ret => ret?,
+            };
+            Ok(ReserveResponse {
+                reservation: Some(reservation),
+            })
+        })
+        .await
     }
 
-    /// confirm a pending reservation, if reservation is not pending, do nothing
+    /// confirm a pending reservation. an already confirmed one is returned unchanged, other states are NotPending
     async fn confirm(
         &self,
         request: Request<ConfirmRequest>,
     ) -> std::result::Result<Response<ConfirmResponse>, Status> {
-        let request = request.into_inner();
-        let confirm = self.manager.change_status(request.id).await?;
-        Ok(Response::new(ConfirmResponse {
-            reservation: Some(confirm),
-        }))
+        self.idempotent(request.into_inner(), |request| async move {
+            let confirm = self
+                .manager
+                .change_status(
+                    request.id,
+                    expected_version(request.expected_version),
+                    &request.acted_by,
+                )
+                .await?;
+            Ok(ConfirmResponse {
+                reservation: Some(confirm),
+            })
+        })
+        .await
     }
 
     /// update the reservation note
@@ -55,22 +142,64 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<UpdateRequest>,
     ) -> std::result::Result<Response<UpdateResponse>, Status> {
-        let request = request.into_inner();
-        let update = self.manager.update_note(request.id, request.note).await?;
-        Ok(Response::new(UpdateResponse {
-            reservation: Some(update),
-        }))
+        self.idempotent(request.into_inner(), |request| async move {
+            let update = self
+                .manager
+                .update_note(
+                    request.id,
+                    request.note,
+                    expected_version(request.expected_version),
+                    &request.acted_by,
+                )
+                .await?;
+            Ok(UpdateResponse {
+                reservation: Some(update),
+            })
+        })
+        .await
     }
+    /// move a reservation to another time
+    async fn reschedule(
+        &self,
+        request: Request<RescheduleRequest>,
+    ) -> std::result::Result<Response<RescheduleResponse>, Status> {
+        self.idempotent(request.into_inner(), |request| async move {
+            let reservation = self
+                .manager
+                .reschedule(
+                    request.id,
+                    request.start,
+                    request.end,
+                    expected_version(request.expected_version),
+                    &request.acted_by,
+                )
+                .await?;
+            Ok(RescheduleResponse {
+                reservation: Some(reservation),
+            })
+        })
+        .await
+    }
+
     /// cancel a reservation
     async fn cancel(
         &self,
         request: Request<CancelRequest>,
     ) -> std::result::Result<Response<CancelResponse>, Status> {
-        let request = request.into_inner();
-        let delete = self.manager.delete(request.id).await?;
-        Ok(Response::new(CancelResponse {
-            reservation: Some(delete),
-        }))
+        self.idempotent(request.into_inner(), |request| async move {
+            let delete = self
+                .manager
+                .delete(
+                    request.id,
+                    expected_version(request.expected_version),
+                    &request.acted_by,
+                )
+                .await?;
+            Ok(CancelResponse {
+                reservation: Some(delete),
+            })
+        })
+        .await
     }
     /// get a reservation by id
     async fn get(
@@ -91,6 +220,7 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<QueryRequest>,
     ) -> std::result::Result<Response<Self::queryStream>, Status> {
+        let timeout = grpc_timeout(&request);
         let request = request.into_inner();
 
         // make sure query is not empty
@@ -98,10 +228,13 @@ impl ReservationService for RsvpService {
             .query
             .ok_or(Status::invalid_argument("Query is missing"))?;
 
-        let query = self.manager.query(internal_query).await;
+        let query = self
+            .manager
+            .query_with_timeout(internal_query, timeout)
+            .await;
         let stream = ReceiverStream::new(query);
 
-        let transformed_stream = stream.map(|s| s.map_err(tonic::Status::from)).boxed();
+        let transformed_stream = stream.map_err(tonic::Status::from).boxed();
 
         Ok(Response::new(transformed_stream))
     }
@@ -111,11 +244,11 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<FilterRequest>,
     ) -> std::result::Result<Response<FilterResponse>, Status> {
-        let request = request.into_inner();
-        if request.query.is_none() {
-            return Err(Status::invalid_argument("query is required"));
-        }
-        let filter = self.manager.keyset_query(request.query.unwrap()).await?;
+        let query = request
+            .into_inner()
+            .query
+            .ok_or_else(|| Status::invalid_argument("query is required"))?;
+        let filter = self.manager.keyset_query(query).await?;
         Ok(Response::new(FilterResponse {
             reservations: filter.1,
             pager: Some(filter.0),
@@ -131,6 +264,221 @@ impl ReservationService for RsvpService {
     ) -> std::result::Result<Response<Self::listenStream>, Status> {
         todo!()
     }
+
+    /// create or replace the booking policy of a resource
+    async fn set_policy(
+        &self,
+        request: Request<SetPolicyRequest>,
+    ) -> std::result::Result<Response<SetPolicyResponse>, Status> {
+        let policy = request
+            .into_inner()
+            .policy
+            .ok_or_else(|| Status::invalid_argument("policy is required"))?;
+        let policy = self.manager.set_policy(policy).await?;
+        Ok(Response::new(SetPolicyResponse {
+            policy: Some(policy),
+        }))
+    }
+
+    /// get the booking policy of a resource
+    async fn get_policy(
+        &self,
+        request: Request<GetPolicyRequest>,
+    ) -> std::result::Result<Response<GetPolicyResponse>, Status> {
+        let request = request.into_inner();
+        let policy = self.manager.get_policy(request.resource_id).await?;
+        Ok(Response::new(GetPolicyResponse {
+            policy: Some(policy),
+        }))
+    }
+
+    /// replace all holidays of a calendar
+    async fn set_holidays(
+        &self,
+        request: Request<SetHolidaysRequest>,
+    ) -> std::result::Result<Response<SetHolidaysResponse>, Status> {
+        let calendar = request
+            .into_inner()
+            .calendar
+            .ok_or_else(|| Status::invalid_argument("calendar is required"))?;
+        let calendar = self.manager.set_holidays(calendar).await?;
+        Ok(Response::new(SetHolidaysResponse {
+            calendar: Some(calendar),
+        }))
+    }
+
+    /// get all holidays of a calendar
+    async fn get_holidays(
+        &self,
+        request: Request<GetHolidaysRequest>,
+    ) -> std::result::Result<Response<GetHolidaysResponse>, Status> {
+        let request = request.into_inner();
+        let calendar = self.manager.get_holidays(request.calendar_id).await?;
+        Ok(Response::new(GetHolidaysResponse {
+            calendar: Some(calendar),
+        }))
+    }
+
+    /// replace the members of a resource group
+    async fn set_resource_group(
+        &self,
+        request: Request<SetResourceGroupRequest>,
+    ) -> std::result::Result<Response<SetResourceGroupResponse>, Status> {
+        let group = request
+            .into_inner()
+            .group
+            .ok_or_else(|| Status::invalid_argument("group is required"))?;
+        let group = self.manager.set_resource_group(group).await?;
+        Ok(Response::new(SetResourceGroupResponse {
+            group: Some(group),
+        }))
+    }
+
+    /// get the direct members of a resource group
+    async fn get_resource_group(
+        &self,
+        request: Request<GetResourceGroupRequest>,
+    ) -> std::result::Result<Response<GetResourceGroupResponse>, Status> {
+        let request = request.into_inner();
+        let group = self.manager.get_resource_group(request.group_id).await?;
+        Ok(Response::new(GetResourceGroupResponse {
+            group: Some(group),
+        }))
+    }
+
+    /// create or move a resource in the location tree
+    async fn set_resource(
+        &self,
+        request: Request<SetResourceRequest>,
+    ) -> std::result::Result<Response<SetResourceResponse>, Status> {
+        let resource = request
+            .into_inner()
+            .resource
+            .ok_or_else(|| Status::invalid_argument("resource is required"))?;
+        let resource = self.manager.set_resource(resource).await?;
+        Ok(Response::new(SetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// get a resource in the location tree
+    async fn get_resource(
+        &self,
+        request: Request<GetResourceRequest>,
+    ) -> std::result::Result<Response<GetResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = self.manager.get_resource(request.resource_id).await?;
+        Ok(Response::new(GetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// wait for a taken range
+    async fn join_waitlist(
+        &self,
+        request: Request<JoinWaitlistRequest>,
+    ) -> std::result::Result<Response<JoinWaitlistResponse>, Status> {
+        let entry = request
+            .into_inner()
+            .entry
+            .ok_or_else(|| Status::invalid_argument("entry is required"))?;
+        let entry = self.manager.join_waitlist(entry).await?;
+        Ok(Response::new(JoinWaitlistResponse { entry: Some(entry) }))
+    }
+
+    /// remove an entry from the waitlist
+    async fn leave_waitlist(
+        &self,
+        request: Request<LeaveWaitlistRequest>,
+    ) -> std::result::Result<Response<LeaveWaitlistResponse>, Status> {
+        let request = request.into_inner();
+        let entry = self
+            .manager
+            .leave_waitlist(request.id, &request.acted_by)
+            .await?;
+        Ok(Response::new(LeaveWaitlistResponse { entry: Some(entry) }))
+    }
+
+    /// get a waitlist entry
+    async fn get_waitlist_entry(
+        &self,
+        request: Request<GetWaitlistEntryRequest>,
+    ) -> std::result::Result<Response<GetWaitlistEntryResponse>, Status> {
+        let entry = self
+            .manager
+            .get_waitlist_entry(request.into_inner().id)
+            .await?;
+        Ok(Response::new(GetWaitlistEntryResponse {
+            entry: Some(entry),
+        }))
+    }
+
+    /// replace what a delegate may do for the owner
+    async fn set_delegation(
+        &self,
+        request: Request<SetDelegationRequest>,
+    ) -> std::result::Result<Response<SetDelegationResponse>, Status> {
+        let request = request.into_inner();
+        let delegation = request
+            .delegation
+            .ok_or_else(|| Status::invalid_argument("delegation is required"))?;
+        let delegation = self
+            .manager
+            .set_delegation(delegation, &request.acted_by)
+            .await?;
+        Ok(Response::new(SetDelegationResponse {
+            delegation: Some(delegation),
+        }))
+    }
+
+    /// get what a delegate may do for the owner
+    async fn get_delegation(
+        &self,
+        request: Request<GetDelegationRequest>,
+    ) -> std::result::Result<Response<GetDelegationResponse>, Status> {
+        let request = request.into_inner();
+        let delegation = self
+            .manager
+            .get_delegation(request.owner_id, request.delegate_id)
+            .await?;
+        Ok(Response::new(GetDelegationResponse {
+            delegation: Some(delegation),
+        }))
+    }
+
+    /// find free resources matching the attributes, best fit first
+    async fn find_available(
+        &self,
+        request: Request<FindAvailableRequest>,
+    ) -> std::result::Result<Response<FindAvailableResponse>, Status> {
+        let resources = self.manager.find_available(request.into_inner()).await?;
+        Ok(Response::new(FindAvailableResponse { resources }))
+    }
+}
+
+// 0 is the proto default, means the client doesn't check the version
+fn expected_version(version: i64) -> Option<i64> {
+    (version > 0).then_some(version)
+}
+
+// the client deadline is sent as "grpc-timeout" header, e.g. "500m" means 500 milliseconds
+// see https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md
+fn grpc_timeout<T>(request: &Request<T>) -> Option<Duration> {
+    let value = request.metadata().get("grpc-timeout")?.to_str().ok()?;
+    if value.is_empty() || !value.is_char_boundary(value.len() - 1) {
+        return None;
+    }
+    let (amount, unit) = value.split_at(value.len() - 1);
+    let amount: u64 = amount.parse().ok()?;
+    match unit {
+        "H" => Some(Duration::from_secs(amount.checked_mul(60 * 60)?)),
+        "M" => Some(Duration::from_secs(amount.checked_mul(60)?)),
+        "S" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_millis(amount)),
+        "u" => Some(Duration::from_micros(amount)),
+        "n" => Some(Duration::from_nanos(amount)),
+        _ => None,
+    }
 }
 
 // // in order to turn mpsc::Receiver<Result<T, abi::Error>> into tonic::Response
@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod test {
     use crate::{tests::test_utils::TestConfig, RsvpService};
-    use abi::{reservation_service_server::ReservationService, Reservation, ReserveRequest};
+    use abi::{
+        reservation_service_server::ReservationService, CancelRequest, Reservation, ReserveRequest,
+        ERROR_CODE_METADATA_KEY,
+    };
 
     #[tokio::test]
     async fn local_test_rpc_reserve_server_should_work() {
@@ -17,9 +20,7 @@ mod test {
             "test this MDFK",
         );
 
-        let request = tonic::Request::new(ReserveRequest {
-            reservation: Some(reservation.clone()),
-        });
+        let request = tonic::Request::new(ReserveRequest::new(reservation.clone()));
 
         let response = service.reserve(request).await.unwrap();
 
@@ -37,4 +38,76 @@ mod test {
 
         config.cleanup().await;
     }
+
+    #[tokio::test]
+    async fn retry_with_idempotency_key_should_replay_the_response() {
+        let config = TestConfig::new().await;
+        config.initialize().await;
+
+        let service = RsvpService::from_config(&config.config).await.unwrap();
+        let request = ReserveRequest {
+            idempotency_key: "mobile-retry-1".to_string(),
+            ..ReserveRequest::new(Reservation::new_pending(
+                "yangid",
+                "Presidential-Suite",
+                "2022-12-25T15:00:00+0800".parse().unwrap(),
+                "2023-1-25T12:00:00+0800".parse().unwrap(),
+                "",
+            ))
+        };
+
+        let first = service
+            .reserve(tonic::Request::new(request.clone()))
+            .await
+            .unwrap()
+            .into_inner();
+        // would conflict with the first one without the key
+        let retry = service
+            .reserve(tonic::Request::new(request.clone()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(first, retry);
+
+        let mut changed = request.clone();
+        changed.reservation.as_mut().unwrap().note = "changed".to_string();
+        let status = service
+            .reserve(tonic::Request::new(changed))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(
+            status.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(),
+            "IDEMPOTENCY_KEY_REUSED"
+        );
+
+        // a failed request is not kept, the same key works once it can succeed
+        let id = first.reservation.unwrap().id;
+        let cancel = CancelRequest {
+            idempotency_key: "cancel-1".to_string(),
+            acted_by: "yangid".to_string(),
+            ..CancelRequest::new(id + 1)
+        };
+        assert!(service
+            .cancel(tonic::Request::new(cancel.clone()))
+            .await
+            .is_err());
+        let cancel = CancelRequest {
+            id,
+            ..cancel.clone()
+        };
+        let canceled = service
+            .cancel(tonic::Request::new(cancel.clone()))
+            .await
+            .unwrap()
+            .into_inner();
+        let replay = service
+            .cancel(tonic::Request::new(cancel))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(canceled, replay);
+
+        config.cleanup().await;
+    }
 }